@@ -0,0 +1,114 @@
+// Platform administration for escrow_core
+// The admin is set by `initialize`, or by the first `migrate` on contracts that
+// predate it. Besides the arbiter registry and fees, the admin can pause the
+// contract during an incident and upgrade its code.
+// Pausing blocks new jobs and payouts to freelancers; clients can still get refunds.
+// Admin rights move in two steps: the current admin proposes, the new admin accepts.
+use soroban_sdk::{contractimpl, Address, BytesN, Env, Symbol};
+
+use crate::{events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient};
+
+#[contractimpl]
+impl EscrowCore {
+    /// Get the platform admin address
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        Self::read_admin(&env)
+    }
+
+    /// Get the admin proposed by `transfer_admin`, if any
+    pub fn get_pending_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&Symbol::new(&env, "pending_admin"))
+    }
+
+    /// Admin: propose a new admin. Takes effect once they call `accept_admin`.
+    pub fn transfer_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        env.storage().instance().set(&Symbol::new(&env, "pending_admin"), &new_admin);
+
+        events::AdminTransferProposed { admin, new_admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Accept the admin role proposed by the current admin
+    pub fn accept_admin(env: Env) -> Result<(), Error> {
+        let new_admin = Self::get_pending_admin(env.clone()).ok_or(Error::NoPendingAdmin)?;
+        new_admin.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let previous_admin = Self::read_admin(&env)?;
+        env.storage().instance().set(&Symbol::new(&env, "admin"), &new_admin);
+        env.storage().instance().remove(&Symbol::new(&env, "pending_admin"));
+
+        events::AdminTransferred { previous_admin, new_admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin: stop new jobs and payouts until `unpause`
+    pub fn pause(env: Env) -> Result<(), Error> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        env.storage().instance().set(&Symbol::new(&env, "paused"), &true);
+
+        events::Paused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin: resume normal operation
+    pub fn unpause(env: Env) -> Result<(), Error> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        env.storage().instance().set(&Symbol::new(&env, "paused"), &false);
+
+        events::Unpaused { admin }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Whether the contract is paused
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "paused"))
+            .unwrap_or(false)
+    }
+
+    /// Admin: replace the contract code. Storage is kept as is.
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        let admin = Self::read_admin(&env)?;
+        admin.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash.clone());
+
+        events::ContractUpgraded { admin, new_wasm_hash }.publish(&env);
+
+        Ok(())
+    }
+}
+
+impl EscrowCore {
+    pub(crate) fn read_admin(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(env, "admin"))
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Fail if the admin has paused new jobs and payouts
+    pub(crate) fn require_not_paused(env: &Env) -> Result<(), Error> {
+        if Self::is_paused(env.clone()) {
+            return Err(Error::ContractPaused);
+        }
+        Ok(())
+    }
+}
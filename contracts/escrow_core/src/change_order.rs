@@ -0,0 +1,341 @@
+// Change orders for escrow_core
+// Either party can propose adding a milestone, removing an unpaid one or changing a
+// milestone's amount and due date; the change applies once the other party accepts.
+// A payer can act for the client; on jobs of an organization's account, `threshold` of
+// its members must sign whatever the client side proposes or accepts. Extra funding is taken from whoever signs for the
+// client side: with the proposal if they propose, otherwise on acceptance. Funds freed
+// by a change are refunded to the client with their yield. Every proposal is kept as
+// the job's amendment history.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, String, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobLedger, JobRole,
+    JobStatus, Milestone, MilestoneSpec, MilestoneStatus, OrgAction,
+};
+
+#[contracttype]
+#[derive(Clone)]
+pub enum MilestoneChange {
+    /// Add a milestone, funded by the client
+    Add(MilestoneSpec),
+    /// Remove an unpaid milestone and refund its amount
+    Remove(u32),
+    /// Set a milestone's amount and due date
+    Update(u32, i128, u64),
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub enum ChangeOrderStatus {
+    Proposed,
+    Accepted,
+    Rejected,
+    Withdrawn,
+}
+
+/// A proposed amendment to a job. `funded` is what the client deposited with the
+/// proposal towards the change.
+#[contracttype]
+#[derive(Clone)]
+pub struct ChangeOrder {
+    pub order_id: u32,
+    pub proposer: Address,
+    pub change: MilestoneChange,
+    pub funded: i128,
+    pub status: ChangeOrderStatus,
+    pub proposed_at: u64,
+    pub closed_at: u64,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Client, payer or freelancer proposes a change to the job's milestones. On an
+    /// organization's job, `signers` are the members signing a client-side proposal.
+    /// Returns the change order's id.
+    pub fn propose_change_order(
+        env: Env,
+        job_id: BytesN<32>,
+        proposer: Address,
+        change: MilestoneChange,
+        signers: Vec<Address>,
+    ) -> Result<u32, Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        proposer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let client_side = Self::acts_for_client(&env, &job, &proposer, JobRole::Payer);
+        if !client_side && proposer != job.freelancer {
+            return Err(Error::Unauthorized);
+        }
+        if client_side {
+            Self::require_organization_signers(&env, &job.client, &signers)?;
+        }
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+        if storage::read_stream(&env, &job_id).is_some() {
+            return Err(Error::InvalidChangeOrder);
+        }
+
+        let delta = Self::change_delta(&job, &change)?;
+
+        // A client-side proposal brings the funding it needs
+        let funded = if client_side && delta > 0 {
+            let mut ledger = storage::read_ledger(&env, &job_id);
+            Self::fund_change(&env, &job, &proposer, &mut ledger, delta);
+            storage::write_ledger(&env, &job_id, &ledger);
+            delta
+        } else {
+            0
+        };
+
+        let mut orders = storage::read_change_orders(&env, &job_id);
+        let order_id = orders.len() + 1;
+        orders.push_back(ChangeOrder {
+            order_id,
+            proposer: proposer.clone(),
+            change: change.clone(),
+            funded,
+            status: ChangeOrderStatus::Proposed,
+            proposed_at: env.ledger().timestamp(),
+            closed_at: 0,
+        });
+        storage::write_change_orders(&env, &job_id, &orders);
+
+        events::ChangeOrderProposed {
+            job_id,
+            order_id,
+            proposer,
+            change,
+        }
+        .publish(&env);
+
+        Ok(order_id)
+    }
+
+    /// The side that did not propose a change order accepts it and the job is amended.
+    /// When the client or a payer accepts, they fund whatever the proposal did not, with
+    /// `signers` the members signing for an organization's job.
+    pub fn accept_change_order(
+        env: Env,
+        job_id: BytesN<32>,
+        order_id: u32,
+        caller: Address,
+        signers: Vec<Address>,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let mut orders = storage::read_change_orders(&env, &job_id);
+        let mut order = Self::open_change_order(&orders, order_id)?;
+
+        Self::require_counterparty(&env, &job, &order, &caller)?;
+        if order.proposer == job.freelancer {
+            Self::require_organization_signers(&env, &job.client, &signers)?;
+        }
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        // The job may have changed since the proposal
+        let delta = Self::change_delta(&job, &order.change)?;
+        let required = delta.max(0);
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        if required > order.funded {
+            if caller == job.freelancer {
+                return Err(Error::InvalidChangeOrder);
+            }
+            Self::fund_change(&env, &job, &caller, &mut ledger, required - order.funded);
+        }
+
+        match &order.change {
+            MilestoneChange::Add(spec) => {
+                job.milestones.push_back(Milestone {
+                    milestone_id: Self::next_milestone_id(&env, &job),
+                    amount: spec.amount,
+                    title_hash: spec.title_hash.clone(),
+                    due_date: spec.due_date,
+                    proof_url: String::from_str(&env, ""),
+                    submitted_at: 0,
+                    status: MilestoneStatus::Pending,
+                });
+            }
+            MilestoneChange::Remove(milestone_id) => {
+                let (index, _) = Self::find_milestone(&job, *milestone_id)?;
+                // Keep the removed id from being handed out again
+                storage::write_milestone_counter(&env, &job_id, Self::last_milestone_id(&env, &job));
+                job.milestones.remove(index);
+
+                storage::remove_proofs(&env, &job_id, *milestone_id);
+                storage::remove_milestone_payees(&env, &job_id, *milestone_id);
+                Self::remove_time_log(&env, &job_id, *milestone_id);
+                storage::remove_approvals(&env, &job_id, *milestone_id, &OrgAction::Approve);
+                storage::remove_approvals(&env, &job_id, *milestone_id, &OrgAction::Release);
+            }
+            MilestoneChange::Update(milestone_id, amount, due_date) => {
+                let (index, mut milestone) = Self::find_milestone(&job, *milestone_id)?;
+                milestone.amount = *amount;
+                milestone.due_date = *due_date;
+                job.milestones.set(index, milestone);
+            }
+        }
+        job.total_amount += delta;
+
+        // Refund funds the change frees up and any funding beyond what it needed
+        let refund_amount = (order.funded - required).max(0) + (-delta).max(0);
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        if Self::all_milestones_settled(&env, &job) {
+            job.status = JobStatus::Completed;
+        }
+        storage::write_job(&env, &job);
+
+        order.status = ChangeOrderStatus::Accepted;
+        order.closed_at = env.ledger().timestamp();
+        orders.set(order_id - 1, order);
+        storage::write_change_orders(&env, &job_id, &orders);
+
+        events::ChangeOrderAccepted {
+            job_id,
+            order_id,
+            total_amount: job.total_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// The side that did not propose a change order turns it down
+    pub fn reject_change_order(
+        env: Env,
+        job_id: BytesN<32>,
+        order_id: u32,
+        caller: Address,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let order = Self::open_change_order(&storage::read_change_orders(&env, &job_id), order_id)?;
+
+        Self::require_counterparty(&env, &job, &order, &caller)?;
+        storage::extend_instance_ttl(&env);
+
+        Self::close_change_order(&env, &mut job, order, ChangeOrderStatus::Rejected)
+    }
+
+    /// The proposer of a change order takes it back
+    pub fn withdraw_change_order(env: Env, job_id: BytesN<32>, order_id: u32) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let order = Self::open_change_order(&storage::read_change_orders(&env, &job_id), order_id)?;
+
+        order.proposer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        Self::close_change_order(&env, &mut job, order, ChangeOrderStatus::Withdrawn)
+    }
+
+    /// Get every change order proposed on a job, oldest first
+    pub fn get_change_orders(env: Env, job_id: BytesN<32>) -> Vec<ChangeOrder> {
+        storage::read_change_orders(&env, &job_id)
+    }
+}
+
+impl EscrowCore {
+    /// Check a change against the job's current milestones and return how much it
+    /// adds to (or, when negative, removes from) the job's total
+    fn change_delta(job: &Job, change: &MilestoneChange) -> Result<i128, Error> {
+        match change {
+            MilestoneChange::Add(spec) => {
+                if spec.amount <= 0 {
+                    return Err(Error::InvalidMilestoneAmount);
+                }
+                Ok(spec.amount)
+            }
+            MilestoneChange::Remove(milestone_id) => {
+                let milestone = Self::amendable_milestone(job, *milestone_id)?;
+                if job.milestones.len() == 1 {
+                    return Err(Error::InvalidChangeOrder);
+                }
+                Ok(-milestone.amount)
+            }
+            MilestoneChange::Update(milestone_id, amount, _) => {
+                let milestone = Self::amendable_milestone(job, *milestone_id)?;
+                if *amount <= 0 {
+                    return Err(Error::InvalidMilestoneAmount);
+                }
+                Ok(*amount - milestone.amount)
+            }
+        }
+    }
+
+    /// A milestone whose funds are still in escrow and not under dispute
+    fn amendable_milestone(job: &Job, milestone_id: u32) -> Result<Milestone, Error> {
+        let (_, milestone) = Self::find_milestone(job, milestone_id)?;
+        if milestone.status.is_settled() || milestone.status == MilestoneStatus::Disputed {
+            return Err(Error::MilestoneNotAmendable);
+        }
+        Ok(milestone)
+    }
+
+    fn open_change_order(orders: &Vec<ChangeOrder>, order_id: u32) -> Result<ChangeOrder, Error> {
+        let order = orders
+            .get(order_id.wrapping_sub(1))
+            .ok_or(Error::ChangeOrderNotFound)?;
+        if order.status != ChangeOrderStatus::Proposed {
+            return Err(Error::ChangeOrderNotOpen);
+        }
+        Ok(order)
+    }
+
+    /// Require `caller`'s authorization on the side that did not propose the order:
+    /// the client or a payer for a freelancer's proposal, the freelancer otherwise
+    fn require_counterparty(
+        env: &Env,
+        job: &Job,
+        order: &ChangeOrder,
+        caller: &Address,
+    ) -> Result<(), Error> {
+        if order.proposer == job.freelancer {
+            return Self::require_client_side(env, job, caller, JobRole::Payer);
+        }
+        caller.require_auth();
+        if *caller != job.freelancer {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Close a change order without applying it and refund the funding it held.
+    /// Funding already refunded by a cancellation is not refunded twice.
+    fn close_change_order(env: &Env, job: &mut Job, mut order: ChangeOrder, status: ChangeOrderStatus) -> Result<(), Error> {
+        let mut ledger = storage::read_ledger(env, &job.job_id);
+        let refund_amount = order.funded.min(ledger.balance());
+        Self::refund_client(env, job, &mut ledger, refund_amount)?;
+        storage::write_ledger(env, &job.job_id, &ledger);
+        storage::write_job(env, job);
+
+        let order_id = order.order_id;
+        order.status = status.clone();
+        order.closed_at = env.ledger().timestamp();
+        let mut orders = storage::read_change_orders(env, &job.job_id);
+        orders.set(order_id - 1, order);
+        storage::write_change_orders(env, &job.job_id, &orders);
+
+        events::ChangeOrderClosed {
+            job_id: job.job_id.clone(),
+            order_id,
+            status,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Move `amount` from `from` into the job's strategy
+    fn fund_change(env: &Env, job: &Job, from: &Address, ledger: &mut JobLedger, amount: i128) {
+        Self::deposit_principal(env, job, from, amount);
+        ledger.funded += amount;
+        Self::adjust_total_escrowed(env, amount);
+    }
+}
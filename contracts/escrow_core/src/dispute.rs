@@ -0,0 +1,313 @@
+// Dispute resolution for escrow_core
+// Either party can dispute a milestone. While the dispute is open the job is `Disputed`
+// and no milestone can be approved or released; the arbiter settles the disputed
+// milestone by splitting its escrowed amount between freelancer and client.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec};
+
+use crate::{events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus, MilestoneStatus};
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub enum DisputeStatus {
+    Open,
+    Resolved,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct DisputeEvidence {
+    pub submitted_by: Address,
+    pub evidence_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Dispute {
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub raised_by: Address,
+    pub reason_hash: BytesN<32>,
+    pub arbiter: Address,
+    pub status: DisputeStatus,
+    pub evidence: Vec<DisputeEvidence>,
+    pub raised_at: u64,
+    pub resolved_at: u64,
+    pub freelancer_amount: i128,
+    pub client_amount: i128,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Admin: add an arbiter to the platform registry
+    pub fn add_arbiter(env: Env, arbiter: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut arbiters = Self::get_arbiters(env.clone());
+        if arbiters.contains(&arbiter) {
+            return Err(Error::ArbiterAlreadyRegistered);
+        }
+        arbiters.push_back(arbiter.clone());
+        env.storage().instance().set(&Symbol::new(&env, "arbiters"), &arbiters);
+
+        events::ArbiterAdded { arbiter }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin: remove an arbiter from the platform registry
+    pub fn remove_arbiter(env: Env, arbiter: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut arbiters = Self::get_arbiters(env.clone());
+        let index = arbiters
+            .first_index_of(&arbiter)
+            .ok_or(Error::ArbiterNotRegistered)?;
+        arbiters.remove(index);
+        env.storage().instance().set(&Symbol::new(&env, "arbiters"), &arbiters);
+
+        events::ArbiterRemoved { arbiter }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the platform arbiter registry
+    pub fn get_arbiters(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "arbiters"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Assign a dedicated arbiter to a job. Both parties must agree.
+    /// Jobs without one fall back to the platform registry when a dispute is raised.
+    pub fn assign_arbiter(env: Env, job_id: BytesN<32>, arbiter: Address) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Require authorization from both parties
+        job.client.require_auth();
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+        if arbiter == job.client || arbiter == job.freelancer {
+            return Err(Error::ArbiterNotIndependent);
+        }
+
+        storage::write_job_arbiter(&env, &job_id, &arbiter);
+
+        events::ArbiterAssigned { job_id, arbiter }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the arbiter assigned to a job, if any
+    pub fn get_job_arbiter(env: Env, job_id: BytesN<32>) -> Option<Address> {
+        storage::read_job_arbiter(&env, &job_id)
+    }
+
+    /// Raise a dispute on a milestone that has not been paid yet.
+    /// Callable by the client or the freelancer; freezes the job until resolved.
+    pub fn raise_dispute(
+        env: Env,
+        caller: Address,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        reason_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        caller.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if caller != job.client && caller != job.freelancer {
+            return Err(Error::Unauthorized);
+        }
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let (index, mut milestone) = Self::find_milestone(&job, milestone_id)?;
+        match milestone.status {
+            MilestoneStatus::Pending | MilestoneStatus::ProofSubmitted | MilestoneStatus::Approved => {}
+            _ => return Err(Error::MilestoneNotDisputable),
+        }
+
+        let arbiter = Self::select_arbiter(&env, &job)?;
+
+        milestone.status = MilestoneStatus::Disputed;
+        job.milestones.set(index, milestone);
+        job.status = JobStatus::Disputed;
+        storage::write_job(&env, &job);
+
+        let dispute = Dispute {
+            job_id: job_id.clone(),
+            milestone_id,
+            raised_by: caller.clone(),
+            reason_hash: reason_hash.clone(),
+            arbiter: arbiter.clone(),
+            status: DisputeStatus::Open,
+            evidence: Vec::new(&env),
+            raised_at: env.ledger().timestamp(),
+            resolved_at: 0,
+            freelancer_amount: 0,
+            client_amount: 0,
+        };
+        storage::write_dispute(&env, &dispute);
+
+        events::DisputeRaised {
+            job_id,
+            milestone_id,
+            raised_by: caller,
+            arbiter,
+            reason_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Submit evidence for an open dispute. Callable by either party.
+    pub fn submit_evidence(
+        env: Env,
+        caller: Address,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        caller.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if caller != job.client && caller != job.freelancer {
+            return Err(Error::Unauthorized);
+        }
+
+        let mut dispute = Self::get_dispute(env.clone(), job_id.clone(), milestone_id)?;
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::DisputeNotOpen);
+        }
+
+        dispute.evidence.push_back(DisputeEvidence {
+            submitted_by: caller.clone(),
+            evidence_hash: evidence_hash.clone(),
+            submitted_at: env.ledger().timestamp(),
+        });
+        storage::write_dispute(&env, &dispute);
+
+        events::EvidenceSubmitted {
+            job_id,
+            milestone_id,
+            submitted_by: caller,
+            evidence_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Arbiter settles a dispute: `freelancer_amount` of the milestone is paid to the
+    /// freelancer (less the job's fee) and the rest is refunded to the client, both out of escrow.
+    pub fn resolve_dispute(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        freelancer_amount: i128,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let mut dispute = Self::get_dispute(env.clone(), job_id.clone(), milestone_id)?;
+
+        // Require arbiter authorization
+        dispute.arbiter.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+
+        if dispute.status != DisputeStatus::Open {
+            return Err(Error::DisputeNotOpen);
+        }
+
+        let (index, mut milestone) = Self::find_milestone(&job, milestone_id)?;
+        if freelancer_amount < 0 || freelancer_amount > milestone.amount {
+            return Err(Error::InvalidSplit);
+        }
+        let client_amount = milestone.amount - freelancer_amount;
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        if ledger.balance() < milestone.amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        if freelancer_amount > 0 {
+            let (yield_amount, _) = Self::pay_freelancer(&env, &job, milestone_id, freelancer_amount, None)?;
+            ledger.released += freelancer_amount;
+            ledger.yield_attributed += yield_amount;
+            job.yield_earned += yield_amount;
+        }
+        Self::refund_client(&env, &mut job, &mut ledger, client_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+        Self::adjust_total_escrowed(&env, -freelancer_amount);
+
+        milestone.status = MilestoneStatus::Resolved;
+        job.milestones.set(index, milestone);
+        job.status = if Self::all_milestones_settled(&env, &job) {
+            JobStatus::Completed
+        } else {
+            JobStatus::Active
+        };
+        storage::write_job(&env, &job);
+
+        dispute.status = DisputeStatus::Resolved;
+        dispute.resolved_at = env.ledger().timestamp();
+        dispute.freelancer_amount = freelancer_amount;
+        dispute.client_amount = client_amount;
+        storage::write_dispute(&env, &dispute);
+
+        events::DisputeResolved {
+            job_id,
+            milestone_id,
+            arbiter: dispute.arbiter,
+            freelancer_amount,
+            client_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the dispute raised on a milestone
+    pub fn get_dispute(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<Dispute, Error> {
+        storage::read_dispute(&env, &job_id, milestone_id)
+            .ok_or(Error::DisputeNotFound)
+    }
+}
+
+impl EscrowCore {
+    /// Pick the job's own arbiter, or rotate through the platform registry, passing
+    /// over the job's client and freelancer
+    fn select_arbiter(env: &Env, job: &Job) -> Result<Address, Error> {
+        if let Some(arbiter) = storage::read_job_arbiter(env, &job.job_id) {
+            return Ok(arbiter);
+        }
+
+        let mut arbiters = Vec::new(env);
+        for arbiter in Self::get_arbiters(env.clone()).iter() {
+            if arbiter != job.client && arbiter != job.freelancer {
+                arbiters.push_back(arbiter);
+            }
+        }
+        if arbiters.is_empty() {
+            return Err(Error::NoArbiterAvailable);
+        }
+
+        let counter_key = Symbol::new(env, "dispute_counter");
+        let counter: u32 = env.storage().instance().get(&counter_key).unwrap_or(0);
+        env.storage().instance().set(&counter_key, &(counter + 1));
+
+        Ok(arbiters.get(counter % arbiters.len()).unwrap())
+    }
+}
@@ -0,0 +1,85 @@
+// Error codes returned by escrow_core entrypoints.
+// Codes are part of the public interface: never renumber, only append.
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    JobNotFound = 4,
+    JobNotActive = 5,
+    MilestoneNotFound = 6,
+    NoMilestones = 7,
+    InvalidMilestoneAmount = 8,
+    MilestoneTotalMismatch = 9,
+    InvalidReviewPeriod = 10,
+    MilestoneNotAwaitingProof = 11,
+    ProofNotSubmitted = 12,
+    MilestoneNotApproved = 13,
+    ReviewPeriodNotOver = 14,
+    MilestoneNotClaimable = 15,
+    MilestoneNotReclaimable = 16,
+    MilestoneNotExpired = 17,
+    InsufficientPrincipal = 18,
+    ArbiterAlreadyRegistered = 19,
+    ArbiterNotRegistered = 20,
+    ArbiterNotIndependent = 21,
+    NoArbiterAvailable = 22,
+    MilestoneNotDisputable = 23,
+    DisputeNotFound = 24,
+    DisputeNotOpen = 25,
+    InvalidSplit = 26,
+    StrategyNotApproved = 27,
+    StrategyAlreadyApproved = 28,
+    ContractPaused = 29,
+    NoPendingAdmin = 30,
+    FeeTooHigh = 31,
+    TreasuryNotSet = 32,
+    MigrationPending = 33,
+    DeadlineExpired = 34,
+    SlippageExceeded = 35,
+    InvalidPayoutProfile = 36,
+    InvalidPayees = 37,
+    MilestoneNotAmendable = 38,
+    InvalidStream = 39,
+    NotAStream = 40,
+    InvalidHourlyTerms = 41,
+    NotHourly = 42,
+    InvalidTimeLog = 43,
+    WeeklyCapExceeded = 44,
+    HourlyBudgetExceeded = 45,
+    TimeLogsPending = 46,
+    ChangeOrderNotFound = 47,
+    ChangeOrderNotOpen = 48,
+    InvalidChangeOrder = 49,
+    JobNotOpen = 50,
+    InvalidExpiry = 51,
+    PostingExpired = 52,
+    PostingNotExpired = 53,
+    InvalidProposal = 54,
+    ProposalNotFound = 55,
+    JobNotPending = 56,
+    TermsMismatch = 57,
+    AcceptanceExpired = 58,
+    AcceptanceNotExpired = 59,
+    InvalidOrganization = 60,
+    NotOrganizationMember = 61,
+    AlreadySigned = 62,
+    NotSigned = 63,
+    OrganizationApprovalRequired = 64,
+    RoleAlreadyGranted = 65,
+    RoleNotGranted = 66,
+    InvalidRoleAccount = 67,
+    InvalidYieldPolicy = 68,
+    JobNotFinished = 69,
+    MilestonesInReview = 70,
+    ConversionFailed = 71,
+    TooManyProposals = 72,
+    MilestoneCountMismatch = 73,
+    InvalidJobOptions = 74,
+    HoursOverflow = 75,
+    TooManyTimeLogs = 76,
+}
@@ -0,0 +1,395 @@
+// Contract events emitted by escrow_core
+use soroban_sdk::{contractevent, Address, BytesN, String, Vec};
+
+use crate::{ChangeOrderStatus, JobRole, MilestoneChange, OrgAction, Payee, PayeeAmount, YieldStrategy};
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ProofSubmitted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub revision: u32,
+    pub proof_uri: String,
+    pub content_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MilestoneRejected {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub revision: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MilestoneClaimed {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MilestoneExpired {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ArbiterAssigned {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub arbiter: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct DisputeRaised {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub raised_by: Address,
+    pub arbiter: Address,
+    pub reason_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct EvidenceSubmitted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub submitted_by: Address,
+    pub evidence_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct DisputeResolved {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub arbiter: Address,
+    pub freelancer_amount: i128,
+    pub client_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AdminTransferProposed {
+    #[topic]
+    pub admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AdminTransferred {
+    #[topic]
+    pub previous_admin: Address,
+    pub new_admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AdminSet {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct Paused {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct Unpaused {
+    #[topic]
+    pub admin: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ContractUpgraded {
+    #[topic]
+    pub admin: Address,
+    pub new_wasm_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct SchemaMigrated {
+    pub from_version: u32,
+    pub to_version: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct PlatformFeeSet {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ClientFeeSet {
+    #[topic]
+    pub client: Address,
+    pub fee_bps: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ClientFeeCleared {
+    #[topic]
+    pub client: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ArbiterAdded {
+    #[topic]
+    pub arbiter: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ArbiterRemoved {
+    #[topic]
+    pub arbiter: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct StrategyApproved {
+    #[topic]
+    pub strategy: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct StrategyRevoked {
+    #[topic]
+    pub strategy: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct AssetStrategySet {
+    #[topic]
+    pub asset: Address,
+    pub strategy: YieldStrategy,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct FeeCollected {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub treasury: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct PaymentReleased {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub recipient: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MilestoneReleased {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub fee: i128,
+    pub payouts: Vec<PayeeAmount>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct MilestonePayeesAmended {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub payees: Vec<Payee>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct StreamStopped {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub vested_amount: i128,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct TimeLogSubmitted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub week: u32,
+    pub hours: u32,
+    pub diary_hash: BytesN<32>,
+    pub amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct HourlyJobClosed {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ChangeOrderProposed {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub order_id: u32,
+    pub proposer: Address,
+    pub change: MilestoneChange,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ChangeOrderAccepted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub order_id: u32,
+    pub total_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ChangeOrderClosed {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub order_id: u32,
+    pub status: ChangeOrderStatus,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ProposalSubmitted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub freelancer: Address,
+    pub price: i128,
+    pub timeline: u64,
+    pub proposal_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ProposalAccepted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub freelancer: Address,
+    pub price: i128,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct PostingExpired {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct JobAccepted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub freelancer: Address,
+    pub terms_hash: BytesN<32>,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct JobWithdrawn {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub refund_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ApprovalSigned {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub action: OrgAction,
+    pub member: Address,
+    pub approvals: u32,
+    pub threshold: u32,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct ApprovalRevoked {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub milestone_id: u32,
+    pub action: OrgAction,
+    pub member: Address,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct RoleGranted {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub account: Address,
+    pub role: JobRole,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct RoleRevoked {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub account: Address,
+    pub role: JobRole,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct YieldDistributed {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub client_amount: i128,
+    pub freelancer_amount: i128,
+    pub platform_amount: i128,
+}
+
+#[contractevent]
+#[derive(Clone)]
+pub struct JobSettled {
+    #[topic]
+    pub job_id: BytesN<32>,
+    pub yield_swept: i128,
+    pub yield_earned: i128,
+    pub yield_unclaimed: i128,
+}
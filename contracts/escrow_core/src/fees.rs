@@ -0,0 +1,135 @@
+// Platform fees for escrow_core
+// The admin sets a fee in basis points and a treasury. Each job snapshots the rate
+// that applies to its client when it is created, so later changes never affect
+// funded jobs. The fee is taken from every payout to the freelancer.
+use soroban_sdk::{contractimpl, Address, Env, Symbol};
+
+use crate::{
+    events, payout::Conversion, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job,
+    BPS_DENOMINATOR,
+};
+
+/// Highest fee the admin can set: 10%
+pub const MAX_FEE_BPS: u32 = 1_000;
+
+#[contractimpl]
+impl EscrowCore {
+    /// Admin: set the platform fee and the treasury that receives it
+    pub fn set_platform_fee(env: Env, fee_bps: u32, treasury: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeTooHigh);
+        }
+
+        env.storage().instance().set(&Symbol::new(&env, "fee_bps"), &fee_bps);
+        env.storage().instance().set(&Symbol::new(&env, "treasury"), &treasury);
+
+        events::PlatformFeeSet { fee_bps, treasury }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the platform fee in basis points
+    pub fn get_platform_fee(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "fee_bps"))
+            .unwrap_or(0)
+    }
+
+    /// Get the treasury address, if configured
+    pub fn get_treasury(env: Env) -> Option<Address> {
+        env.storage().instance().get(&Symbol::new(&env, "treasury"))
+    }
+
+    /// Admin: charge a client a different fee than the platform default
+    pub fn set_client_fee(env: Env, client: Address, fee_bps: u32) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if fee_bps > MAX_FEE_BPS {
+            return Err(Error::FeeTooHigh);
+        }
+        if fee_bps > 0 && Self::get_treasury(env.clone()).is_none() {
+            return Err(Error::TreasuryNotSet);
+        }
+
+        storage::write_client_fee(&env, &client, fee_bps);
+
+        events::ClientFeeSet { client, fee_bps }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin: return a client to the platform default fee
+    pub fn clear_client_fee(env: Env, client: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        storage::remove_client_fee(&env, &client);
+
+        events::ClientFeeCleared { client }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get a client's fee override, if any
+    pub fn get_client_fee(env: Env, client: Address) -> Option<u32> {
+        storage::read_client_fee(&env, &client)
+    }
+
+    /// Total fees sent to the treasury in `asset`
+    pub fn get_collected_fees(env: Env, asset: Address) -> i128 {
+        storage::read_collected_fees(&env, &asset)
+    }
+}
+
+impl EscrowCore {
+    /// Fee rate a new job for `client` is created with
+    pub(crate) fn fee_rate_for(env: &Env, client: &Address) -> u32 {
+        storage::read_client_fee(env, client).unwrap_or_else(|| Self::get_platform_fee(env.clone()))
+    }
+
+    /// Pay `amount` of a job's principal to the milestone's payees less the job's fee,
+    /// which is sent to the treasury. Returns the yield shared out with it and the
+    /// amount delivered to the payees.
+    pub(crate) fn pay_freelancer(
+        env: &Env,
+        job: &Job,
+        milestone_id: u32,
+        amount: i128,
+        conversion: Option<Conversion>,
+    ) -> Result<(i128, i128), Error> {
+        let fee = amount * job.fee_bps as i128 / BPS_DENOMINATOR as i128;
+        let (mut yield_amount, delivered, payouts) =
+            Self::pay_payees(env, job, milestone_id, amount - fee, conversion)?;
+
+        if fee > 0 {
+            let treasury = Self::get_treasury(env.clone()).ok_or(Error::TreasuryNotSet)?;
+            yield_amount += Self::withdraw_principal(env, job, fee, &treasury)?;
+
+            let collected = storage::read_collected_fees(env, &job.asset_address);
+            storage::write_collected_fees(env, &job.asset_address, collected + fee);
+
+            events::FeeCollected {
+                job_id: job.job_id.clone(),
+                milestone_id,
+                treasury,
+                amount: fee,
+            }
+            .publish(env);
+        }
+
+        events::MilestoneReleased {
+            job_id: job.job_id.clone(),
+            milestone_id,
+            fee,
+            payouts,
+        }
+        .publish(env);
+
+        Ok((yield_amount, delivered))
+    }
+}
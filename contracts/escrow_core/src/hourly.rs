@@ -0,0 +1,290 @@
+// Hourly jobs for escrow_core
+// The client funds a budget and sets an hourly rate and a cap on the hours billed per
+// week, counted from the job's creation. Each time log the freelancer submits becomes
+// a milestone awaiting review, worth its hours at the job's rate, and then follows the
+// usual flow: the client can approve, reject or dispute it during the review period.
+// Logs that are approved or left undisputed are paid by `settle_time_logs`, which
+// anyone can call. The job stays open until the client closes it.
+// A job holds at most `MAX_TIME_LOGS` logs so that it stays readable.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Map, String, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus,
+    Milestone, MilestoneStatus, ProofAttachment,
+};
+
+const WEEK: u64 = 7 * 24 * 60 * 60;
+
+/// Most time logs an hourly job accepts
+pub const MAX_TIME_LOGS: u32 = 100;
+
+/// Terms of an hourly job and the hours logged against each week
+#[contracttype]
+#[derive(Clone)]
+pub struct HourlyTerms {
+    pub hourly_rate: i128,
+    pub weekly_cap: u32,
+    pub logged_hours: Map<u32, u32>,
+}
+
+/// Hours billed by the milestone created for a time log
+#[contracttype]
+#[derive(Clone)]
+pub struct TimeLog {
+    pub week: u32,
+    pub hours: u32,
+    pub diary_hash: BytesN<32>,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Create an hourly job funded with `budget`, billing `hourly_rate` per hour for at
+    /// most `weekly_cap` hours a week. Each time log can be reviewed for `review_period`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_hourly_job(
+        env: Env,
+        client: Address,
+        freelancer: Address,
+        budget: i128,
+        asset_address: Address,
+        hourly_rate: i128,
+        weekly_cap: u32,
+        review_period: u64,
+    ) -> Result<BytesN<32>, Error> {
+        client.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+        Self::require_current_schema(&env)?;
+
+        if budget <= 0 || hourly_rate <= 0 || weekly_cap == 0 {
+            return Err(Error::InvalidHourlyTerms);
+        }
+        if review_period == 0 {
+            return Err(Error::InvalidReviewPeriod);
+        }
+        let strategy = Self::resolve_strategy(&env, &asset_address, None)?;
+
+        let job_id = Self::fund_job(
+            &env,
+            client,
+            freelancer,
+            budget,
+            asset_address,
+            Vec::<Milestone>::new(&env),
+            review_period,
+            strategy,
+        );
+
+        storage::write_hourly_terms(
+            &env,
+            &job_id,
+            &HourlyTerms {
+                hourly_rate,
+                weekly_cap,
+                logged_hours: Map::new(&env),
+            },
+        );
+
+        Ok(job_id)
+    }
+
+    /// Freelancer logs `hours` worked in `week` (0 is the week the job was created),
+    /// committing to the work diary with `diary_hash`. Returns the id of the milestone
+    /// created for the log.
+    pub fn submit_time_log(
+        env: Env,
+        job_id: BytesN<32>,
+        week: u32,
+        hours: u32,
+        diary_hash: BytesN<32>,
+    ) -> Result<u32, Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let mut terms = storage::read_hourly_terms(&env, &job_id).ok_or(Error::NotHourly)?;
+
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let current_week = ((now - job.created_at) / WEEK) as u32;
+        if hours == 0 || week > current_week {
+            return Err(Error::InvalidTimeLog);
+        }
+        if job.milestones.len() >= MAX_TIME_LOGS {
+            return Err(Error::TooManyTimeLogs);
+        }
+
+        let logged = terms.logged_hours.get(week).unwrap_or(0);
+        let logged_total = logged.checked_add(hours).ok_or(Error::HoursOverflow)?;
+        if logged_total > terms.weekly_cap {
+            return Err(Error::WeeklyCapExceeded);
+        }
+
+        // Logs are billed against the part of the budget no earlier log has claimed
+        let amount = terms
+            .hourly_rate
+            .checked_mul(hours as i128)
+            .ok_or(Error::HourlyBudgetExceeded)?;
+        let allocated: i128 = job.milestones.iter().map(|m| m.amount).sum();
+        if allocated + amount > job.total_amount {
+            return Err(Error::HourlyBudgetExceeded);
+        }
+
+        let milestone_id = Self::next_milestone_id(&env, &job);
+        job.milestones.push_back(Milestone {
+            milestone_id,
+            amount,
+            title_hash: diary_hash.clone(),
+            due_date: 0,
+            proof_url: String::from_str(&env, ""),
+            submitted_at: now,
+            status: MilestoneStatus::ProofSubmitted,
+        });
+        storage::write_job(&env, &job);
+
+        let mut proofs = Vec::new(&env);
+        proofs.push_back(ProofAttachment {
+            revision: 1,
+            uri: String::from_str(&env, ""),
+            content_hash: diary_hash.clone(),
+            submitted_at: now,
+        });
+        storage::write_proofs(&env, &job_id, milestone_id, &proofs);
+
+        terms.logged_hours.set(week, logged_total);
+        storage::write_hourly_terms(&env, &job_id, &terms);
+        storage::write_time_log(
+            &env,
+            &job_id,
+            milestone_id,
+            &TimeLog {
+                week,
+                hours,
+                diary_hash: diary_hash.clone(),
+            },
+        );
+
+        events::TimeLogSubmitted {
+            job_id,
+            milestone_id,
+            week,
+            hours,
+            diary_hash,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(milestone_id)
+    }
+
+    /// Pay every time log of an hourly job that was approved or whose review period
+    /// passed without a dispute. Anyone can call this. Returns the amount paid out of
+    /// escrow.
+    pub fn settle_time_logs(env: Env, job_id: BytesN<32>) -> Result<i128, Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        storage::read_hourly_terms(&env, &job_id).ok_or(Error::NotHourly)?;
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut paid = 0i128;
+        for index in 0..job.milestones.len() {
+            let milestone = job.milestones.get(index).unwrap();
+            let due = match milestone.status {
+                MilestoneStatus::Approved => true,
+                MilestoneStatus::ProofSubmitted => now >= milestone.submitted_at.saturating_add(job.review_period),
+                _ => false,
+            };
+            if due {
+                Self::pay_milestone(&env, &mut job, index, None)?;
+                paid += milestone.amount;
+            }
+        }
+        storage::write_job(&env, &job);
+
+        Ok(paid)
+    }
+
+    /// Client ends an hourly job once no time log awaits review, payment or a dispute.
+    /// Logs that were rejected and never resubmitted expire, and the rest of the budget
+    /// is refunded with its yield.
+    pub fn close_hourly_job(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        storage::read_hourly_terms(&env, &job_id).ok_or(Error::NotHourly)?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        Self::expire_time_logs(&mut job)?;
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        let refund_amount = ledger.balance();
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        job.status = JobStatus::Completed;
+        storage::write_job(&env, &job);
+
+        events::HourlyJobClosed {
+            job_id,
+            refund_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the terms of an hourly job and the hours logged so far
+    pub fn get_hourly_terms(env: Env, job_id: BytesN<32>) -> Result<HourlyTerms, Error> {
+        storage::read_hourly_terms(&env, &job_id).ok_or(Error::NotHourly)
+    }
+
+    /// Get the time log behind a milestone of an hourly job
+    pub fn get_time_log(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<TimeLog, Error> {
+        storage::read_time_log(&env, &job_id, milestone_id).ok_or(Error::MilestoneNotFound)
+    }
+}
+
+impl EscrowCore {
+    /// Drop the time log behind a milestone removed from a job, giving its hours back
+    /// to the week they were logged in
+    pub(crate) fn remove_time_log(env: &Env, job_id: &BytesN<32>, milestone_id: u32) {
+        let log = match storage::read_time_log(env, job_id, milestone_id) {
+            Some(log) => log,
+            None => return,
+        };
+        if let Some(mut terms) = storage::read_hourly_terms(env, job_id) {
+            let logged = terms.logged_hours.get(log.week).unwrap_or(0);
+            terms.logged_hours.set(log.week, logged.saturating_sub(log.hours));
+            storage::write_hourly_terms(env, job_id, &terms);
+        }
+        storage::remove_time_log(env, job_id, milestone_id);
+    }
+
+    /// Expire time logs that were rejected and never resubmitted. Fails while any log
+    /// still awaits review, payment or a dispute.
+    pub(crate) fn expire_time_logs(job: &mut Job) -> Result<(), Error> {
+        for index in 0..job.milestones.len() {
+            let mut milestone = job.milestones.get(index).unwrap();
+            match milestone.status {
+                MilestoneStatus::Pending => {
+                    milestone.status = MilestoneStatus::Expired;
+                    job.milestones.set(index, milestone);
+                }
+                status if status.is_settled() => {}
+                _ => return Err(Error::TimeLogsPending),
+            }
+        }
+        Ok(())
+    }
+}
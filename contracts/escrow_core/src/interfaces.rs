@@ -0,0 +1,71 @@
+// Cross-contract interfaces used by escrow_core
+// Calls go through the generated clients so that argument mismatches with the
+// yield strategies and router are caught at compile time. Any contract exposing
+// these functions can be plugged in as the backend.
+use soroban_sdk::{contractclient, Address, Env};
+
+/// Standard interface for yield backends. Positions are keyed by `owner` and `token`;
+/// `owner` must authorize deposits, withdrawals and claims.
+#[contractclient(name = "YieldStrategyClient")]
+pub trait YieldStrategyInterface {
+    /// Take `amount` of `token` from `owner` and add it to the owner's position
+    fn strategy_deposit(env: Env, owner: Address, token: Address, amount: i128);
+
+    /// Send `amount` of the owner's principal to `recipient`; returns the amount sent
+    fn strategy_withdraw_principal(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> i128;
+
+    /// Send up to `amount` of the owner's accrued yield to `recipient`; returns the amount sent
+    fn strategy_claim_yield(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> i128;
+
+    /// Principal plus accrued yield currently held for `owner`
+    fn strategy_preview_balance(env: Env, owner: Address, token: Address) -> i128;
+}
+
+/// Fixed-point scale of router exchange rates: a rate of `RATE_SCALE` converts one to one
+pub const RATE_SCALE: i128 = 10_000_000;
+
+#[contractclient(name = "LiquidityRouterClient")]
+pub trait LiquidityRouterInterface {
+    /// Take `amount` of `from_asset` from the caller, convert it into `to_asset` and
+    /// send it to `recipient`. Fails, taking nothing, unless at least `min_amount_out`
+    /// is sent.
+    fn convert_and_send(
+        env: Env,
+        from_asset: Address,
+        to_asset: Address,
+        amount: i128,
+        recipient: Address,
+        min_amount_out: i128,
+    ) -> i128;
+
+    /// Units of `to_asset` per unit of `from_asset`, scaled by `RATE_SCALE`; fails for
+    /// pairs the router does not quote
+    fn get_exchange_rate(env: Env, from_asset: Address, to_asset: Address) -> i128;
+}
+
+/// Deposit bookkeeping of the original YieldHarvester, still holding the escrow of
+/// jobs created before yield strategies existed
+#[contractclient(name = "LegacyHarvesterClient")]
+pub trait LegacyHarvesterInterface {
+    /// Send `amount` of the owner's principal to `principal_recipient` and its yield
+    /// to `yield_recipient`; returns the total sent
+    fn withdraw_to(
+        env: Env,
+        owner: Address,
+        amount: i128,
+        principal_recipient: Address,
+        yield_recipient: Address,
+    ) -> i128;
+}
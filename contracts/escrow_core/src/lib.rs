@@ -1,5 +1,61 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Vec, BytesN, Symbol, token, IntoVal};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, IntoVal, Map, String, TryFromVal, Val, Vec, BytesN, Symbol};
+
+mod admin;
+mod change_order;
+mod dispute;
+mod errors;
+mod events;
+mod fees;
+mod hourly;
+mod interfaces;
+mod migration;
+mod organization;
+mod payees;
+mod payout;
+mod posting;
+mod roles;
+mod storage;
+mod strategy;
+mod stream;
+mod terms;
+mod yield_policy;
+
+/// Review window used by `create_job` when the client does not choose one
+pub const DEFAULT_REVIEW_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+/// Time a named freelancer has to accept a new job when the client sets no deadline
+pub const DEFAULT_ACCEPTANCE_PERIOD: u64 = 7 * 24 * 60 * 60;
+
+/// Time after a milestone's due date before the client may reclaim it if undelivered
+pub const DEADLINE_GRACE_PERIOD: u64 = 3 * 24 * 60 * 60;
+
+/// Basis points in a whole: fees, payee splits, slippage and yield shares are out of this
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Most jobs returned by one call to `get_client_jobs` or `get_freelancer_jobs`
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+pub use change_order::{ChangeOrder, ChangeOrderStatus, MilestoneChange};
+pub use dispute::{Dispute, DisputeEvidence, DisputeStatus};
+pub use errors::Error;
+pub use fees::MAX_FEE_BPS;
+pub use hourly::{HourlyTerms, TimeLog, MAX_TIME_LOGS};
+pub use interfaces::{
+    LegacyHarvesterClient, LegacyHarvesterInterface, LiquidityRouterClient, LiquidityRouterInterface,
+    YieldStrategyClient, YieldStrategyInterface, RATE_SCALE,
+};
+pub use migration::SCHEMA_VERSION;
+pub use organization::{OrgAction, Organization};
+pub use payees::{Payee, PayeeAmount};
+pub use payout::{PayoutProfile, PayoutSplit};
+use payout::Conversion;
+pub use posting::{Posting, Proposal, MAX_PROPOSALS};
+pub use roles::{JobRole, RoleGrant};
+pub use strategy::{YieldIndex, YieldStrategy, YIELD_INDEX_SCALE};
+pub use stream::Stream;
+pub use terms::JobTerms;
+pub use yield_policy::{YieldPolicy, YieldShares};
 
 #[contracttype]
 #[derive(Clone, PartialEq, Eq)]
@@ -8,6 +64,9 @@ pub enum JobStatus {
     Completed,
     Disputed,
     Cancelled,
+    Open,
+    Pending,
+    Settled,
 }
 
 #[contracttype]
@@ -17,6 +76,19 @@ pub enum MilestoneStatus {
     ProofSubmitted,
     Approved,
     Paid,
+    Disputed,
+    Resolved,
+    Expired,
+}
+
+impl MilestoneStatus {
+    /// Whether the milestone's funds have left escrow
+    pub fn is_settled(&self) -> bool {
+        matches!(
+            self,
+            MilestoneStatus::Paid | MilestoneStatus::Resolved | MilestoneStatus::Expired
+        )
+    }
 }
 
 #[contracttype]
@@ -24,10 +96,69 @@ pub enum MilestoneStatus {
 pub struct Milestone {
     pub milestone_id: u32,
     pub amount: i128,
+    pub title_hash: BytesN<32>,
+    pub due_date: u64,
     pub proof_url: String,
+    pub submitted_at: u64,
     pub status: MilestoneStatus,
 }
 
+/// Milestone terms supplied by the client at job creation.
+/// `title_hash` commits to the off-chain title/description; `due_date` of 0 means no deadline.
+#[contracttype]
+#[derive(Clone)]
+pub struct MilestoneSpec {
+    pub amount: i128,
+    pub title_hash: BytesN<32>,
+    pub due_date: u64,
+}
+
+/// Optional settings of a job passed to `create_job`:
+/// - `milestones`: explicit milestone terms, adding up to the job's total
+/// - `review_period`: seconds the client has to review proof, `DEFAULT_REVIEW_PERIOD` if unset
+/// - `payees`: milestone ids (starting at 1) mapped to the payees sharing their payout
+/// - `yield_policy`: how the job's yield is split, all to the client if unset
+/// - `terms`: terms the freelancer accepts, all zeros due in `DEFAULT_ACCEPTANCE_PERIOD` if unset
+/// - `strategy`: an approved strategy to hold the escrow, the asset's default if unset
+///
+/// Contract types can't hold an `Option` of another contract type, so the last three
+/// are vectors of at most one entry, empty when unset.
+#[contracttype]
+#[derive(Clone)]
+pub struct JobOptions {
+    pub milestones: Option<Vec<MilestoneSpec>>,
+    pub review_period: Option<u64>,
+    pub payees: Option<Map<u32, Vec<Payee>>>,
+    pub yield_policy: Vec<YieldPolicy>,
+    pub terms: Vec<JobTerms>,
+    pub strategy: Vec<YieldStrategy>,
+}
+
+impl JobOptions {
+    /// Options leaving every setting at its default
+    pub fn new(env: &Env) -> Self {
+        JobOptions {
+            milestones: None,
+            review_period: None,
+            payees: None,
+            yield_policy: Vec::new(env),
+            terms: Vec::new(env),
+            strategy: Vec::new(env),
+        }
+    }
+}
+
+/// One file submitted as proof of work for a milestone. A milestone keeps every
+/// attachment; `revision` increases each time the freelancer resubmits after a rejection.
+#[contracttype]
+#[derive(Clone)]
+pub struct ProofAttachment {
+    pub revision: u32,
+    pub uri: String,
+    pub content_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
 #[contracttype]
 #[derive(Clone)]
 pub struct Job {
@@ -38,8 +169,30 @@ pub struct Job {
     pub asset_address: Address,
     pub milestones: Vec<Milestone>,
     pub status: JobStatus,
+    pub review_period: u64,
     pub created_at: u64,
     pub yield_earned: i128,
+    pub strategy: YieldStrategy,
+    pub fee_bps: u32,
+    pub terms_hash: BytesN<32>,
+    pub accept_by: u64,
+}
+
+/// Per-job escrow accounting. The job's outstanding balance is
+/// `funded - released - refunded`.
+#[contracttype]
+#[derive(Clone, Default)]
+pub struct JobLedger {
+    pub funded: i128,
+    pub released: i128,
+    pub refunded: i128,
+    pub yield_attributed: i128,
+}
+
+impl JobLedger {
+    pub fn balance(&self) -> i128 {
+        self.funded - self.released - self.refunded
+    }
 }
 
 #[contract]
@@ -47,19 +200,21 @@ pub struct EscrowCore;
 
 #[contractimpl]
 impl EscrowCore {
-    /// Initialize contract with YieldHarvester and LiquidityRouter addresses
+    /// Initialize contract with the platform admin, YieldHarvester and LiquidityRouter addresses
     pub fn initialize(
         env: Env,
+        admin: Address,
         yield_harvester: Address,
         liquidity_router: Address,
         usdc_token: String,
         min_lock_period: u64,
-    ) {
+    ) -> Result<(), Error> {
         if env.storage().instance().has(&Symbol::new(&env, "init")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         // Store configuration
+        env.storage().instance().set(&Symbol::new(&env, "admin"), &admin);
         env.storage().instance().set(&Symbol::new(&env, "yield_harvester"), &yield_harvester);
         env.storage().instance().set(&Symbol::new(&env, "liquidity_router"), &liquidity_router);
         env.storage().instance().set(&Symbol::new(&env, "usdc_token"), &usdc_token);
@@ -67,11 +222,19 @@ impl EscrowCore {
 
         // Initialize job counter
         env.storage().instance().set(&Symbol::new(&env, "job_counter"), &0u32);
+        env.storage().instance().set(&Symbol::new(&env, "schema_version"), &SCHEMA_VERSION);
 
         env.storage().instance().set(&Symbol::new(&env, "init"), &());
+        storage::extend_instance_ttl(&env);
+
+        events::AdminSet { admin }.publish(&env);
+
+        Ok(())
     }
 
-    /// Create a new job with escrow
+    /// Create a new job with escrow. Unless `options.milestones` gives explicit terms,
+    /// `total_amount` is split equally over `milestone_count` milestones, any remainder
+    /// of the division going to the last one. Options left unset take their defaults.
     pub fn create_job(
         env: Env,
         client: Address,
@@ -79,33 +242,442 @@ impl EscrowCore {
         total_amount: i128,
         asset_address: Address,
         milestone_count: u32,
-    ) -> BytesN<32> {
+        options: JobOptions,
+    ) -> Result<BytesN<32>, Error> {
+        let specs = match options.milestones {
+            Some(specs) => {
+                if specs.len() != milestone_count {
+                    return Err(Error::MilestoneCountMismatch);
+                }
+                specs
+            }
+            None => Self::equal_milestones(&env, total_amount, milestone_count)?,
+        };
+        let yield_policy = Self::single_option(options.yield_policy)?;
+        let terms = Self::single_option(options.terms)?;
+        let strategy = Self::single_option(options.strategy)?;
+        if let Some(payees) = &options.payees {
+            Self::validate_job_payees(specs.len(), payees)?;
+        }
+        if let Some(policy) = &yield_policy {
+            Self::validate_yield_policy(&env, policy)?;
+        }
+        if let Some(terms) = &terms {
+            Self::validate_terms(&env, terms)?;
+        }
+
+        let job_id = Self::open_job(
+            &env,
+            client,
+            freelancer,
+            total_amount,
+            asset_address,
+            specs,
+            options.review_period.unwrap_or(DEFAULT_REVIEW_PERIOD),
+            strategy,
+        )?;
+
+        if let Some(payees) = options.payees {
+            for (milestone_id, milestone_payees) in payees.iter() {
+                storage::write_milestone_payees(&env, &job_id, milestone_id, &milestone_payees);
+            }
+        }
+        if let Some(policy) = yield_policy {
+            storage::write_yield_policy(&env, &job_id, &policy);
+        }
+        if let Some(terms) = terms {
+            Self::require_acceptance(&env, &job_id, terms)?;
+        }
+
+        Ok(job_id)
+    }
+
+    /// Get job details
+    pub fn get_job(env: Env, job_id: BytesN<32>) -> Result<Job, Error> {
+        Self::require_current_schema(&env)?;
+        storage::read_job(&env, &job_id).ok_or(Error::JobNotFound)
+    }
+
+    /// Extend the storage TTL of a job so it is not archived.
+    /// Archived jobs are restored by submitting a RestoreFootprint operation
+    /// for the keys flagged during simulation, after which this call keeps them live.
+    pub fn bump_job(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id)?;
+        storage::extend_job_ttl(&env, &job);
+        storage::extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the escrow ledger of a job
+    pub fn get_job_ledger(env: Env, job_id: BytesN<32>) -> JobLedger {
+        storage::read_ledger(&env, &job_id)
+    }
+
+    /// Get the principal still held in escrow for a job
+    pub fn get_job_balance(env: Env, job_id: BytesN<32>) -> i128 {
+        storage::read_ledger(&env, &job_id).balance()
+    }
+
+    /// Get the principal held in escrow across all jobs
+    pub fn get_total_escrowed(env: Env) -> i128 {
+        env.storage().instance().get(&Symbol::new(&env, "total_escrowed")).unwrap_or(0)
+    }
+
+    /// Get LiquidityRouter address
+    pub fn get_liquidity_router(env: Env) -> Result<Address, Error> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "liquidity_router"))
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Get the USDC token identifier given at initialization
+    pub fn get_usdc_token(env: Env) -> Result<String, Error> {
+        env.storage().instance()
+            .get(&Symbol::new(&env, "usdc_token"))
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Get current job counter (number of jobs created)
+    pub fn get_job_counter(env: Env) -> u32 {
+        env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0)
+    }
+
+    /// Get up to `limit` of a client's jobs, oldest first, starting at position `start`.
+    /// At most `MAX_PAGE_SIZE` jobs are returned per call.
+    pub fn get_client_jobs(env: Env, client: Address, start: u32, limit: u32) -> Result<Vec<Job>, Error> {
+        Self::require_current_schema(&env)?;
+        let job_ids = storage::read_client_job_ids(&env, &client, start, limit.min(MAX_PAGE_SIZE));
+        Ok(Self::read_jobs(&env, job_ids))
+    }
+
+    /// Get the number of jobs created by a client
+    pub fn get_client_job_count(env: Env, client: Address) -> u32 {
+        storage::read_client_job_count(&env, &client)
+    }
+
+    /// Get up to `limit` of a freelancer's jobs, oldest first, starting at position
+    /// `start`. At most `MAX_PAGE_SIZE` jobs are returned per call.
+    pub fn get_freelancer_jobs(env: Env, freelancer: Address, start: u32, limit: u32) -> Result<Vec<Job>, Error> {
+        Self::require_current_schema(&env)?;
+        let job_ids = storage::read_freelancer_job_ids(&env, &freelancer, start, limit.min(MAX_PAGE_SIZE));
+        Ok(Self::read_jobs(&env, job_ids))
+    }
+
+    /// Get the number of jobs a freelancer has been hired for
+    pub fn get_freelancer_job_count(env: Env, freelancer: Address) -> u32 {
+        storage::read_freelancer_job_count(&env, &freelancer)
+    }
+
+    /// Freelancer submits proof of work for a milestone.
+    /// Further submissions while the milestone awaits review add attachments to the
+    /// same revision; submitting after a rejection starts a new revision.
+    pub fn submit_proof(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        proof_uri: String,
+        content_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Require freelancer authorization
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let (index, mut milestone) = Self::find_milestone(&job, milestone_id)?;
+        let mut proofs = storage::read_proofs(&env, &job_id, milestone_id);
+        let last_revision = proofs.last().map(|p| p.revision).unwrap_or(0);
+
+        let revision = match milestone.status {
+            MilestoneStatus::Pending => last_revision + 1,
+            MilestoneStatus::ProofSubmitted => last_revision,
+            _ => return Err(Error::MilestoneNotAwaitingProof),
+        };
+
+        proofs.push_back(ProofAttachment {
+            revision,
+            uri: proof_uri.clone(),
+            content_hash: content_hash.clone(),
+            submitted_at: env.ledger().timestamp(),
+        });
+        storage::write_proofs(&env, &job_id, milestone_id, &proofs);
+
+        milestone.proof_url = proof_uri.clone();
+        milestone.submitted_at = env.ledger().timestamp();
+        milestone.status = MilestoneStatus::ProofSubmitted;
+        job.milestones.set(index, milestone);
+        storage::write_job(&env, &job);
+
+        events::ProofSubmitted {
+            job_id,
+            milestone_id,
+            revision,
+            proof_uri,
+            content_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get every proof attachment submitted for a milestone, oldest first
+    pub fn get_milestone_proofs(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Vec<ProofAttachment> {
+        storage::read_proofs(&env, &job_id, milestone_id)
+    }
+
+    /// Approve milestone (proof must have been submitted)
+    pub fn approve_milestone(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        
+        // Require client authorization
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_no_organization(&env, &job)?;
+
+        Self::approve(&env, &mut job, milestone_id)
+    }
+
+    /// Reject submitted proof and send the milestone back to the freelancer for revision
+    pub fn reject_milestone(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Require client authorization
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        Self::reject(&env, &mut job, milestone_id)
+    }
+
+    /// Release payment for milestone
+    pub fn release_payment(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+    ) -> Result<(), Error> {
+        Self::release_approved(&env, job_id, milestone_id, None)?;
+        Ok(())
+    }
+
+    /// Freelancer claims payment for a milestone the client left unanswered.
+    /// Approved milestones can be claimed at once; submitted proof is deemed approved
+    /// once the job's review period has passed without approval or dispute.
+    pub fn claim_milestone(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Require freelancer authorization
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let (index, milestone) = Self::find_milestone(&job, milestone_id)?;
+        match milestone.status {
+            MilestoneStatus::Approved => {}
+            MilestoneStatus::ProofSubmitted => {
+                if env.ledger().timestamp() < milestone.submitted_at.saturating_add(job.review_period) {
+                    return Err(Error::ReviewPeriodNotOver);
+                }
+            }
+            _ => return Err(Error::MilestoneNotClaimable),
+        }
+
+        let amount = milestone.amount;
+        Self::pay_milestone(&env, &mut job, index, None)?;
+        storage::write_job(&env, &job);
+
+        events::MilestoneClaimed {
+            job_id,
+            milestone_id,
+            amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Client reclaims the funds of a milestone the freelancer never delivered.
+    /// Allowed once the milestone's due date plus the grace period has passed and no
+    /// proof is awaiting review; the milestone is marked `Expired`.
+    pub fn reclaim_expired(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Require client authorization
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let (index, mut milestone) = Self::find_milestone(&job, milestone_id)?;
+        if milestone.status != MilestoneStatus::Pending {
+            return Err(Error::MilestoneNotReclaimable);
+        }
+        if milestone.due_date == 0 || env.ledger().timestamp() < milestone.due_date.saturating_add(DEADLINE_GRACE_PERIOD) {
+            return Err(Error::MilestoneNotExpired);
+        }
+
+        let refund_amount = milestone.amount;
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        milestone.status = MilestoneStatus::Expired;
+        job.milestones.set(index, milestone);
+        if Self::all_milestones_settled(&env, &job) {
+            job.status = JobStatus::Completed;
+        }
+        storage::write_job(&env, &job);
+
+        events::MilestoneExpired {
+            job_id,
+            milestone_id,
+            refund_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get YieldHarvester address
+    fn get_yield_harvester(env: &Env) -> Result<Address, Error> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "yield_harvester"))
+            .ok_or(Error::NotInitialized)
+    }
+
+    /// Open a funded job: record it, move the client's funds into the job's yield
+    /// strategy and credit the job's ledger
+    #[allow(clippy::too_many_arguments)]
+    fn open_job(
+        env: &Env,
+        client: Address,
+        freelancer: Address,
+        total_amount: i128,
+        asset_address: Address,
+        specs: Vec<MilestoneSpec>,
+        review_period: u64,
+        strategy: Option<YieldStrategy>,
+    ) -> Result<BytesN<32>, Error> {
         // Require client authorization for this transaction
         client.require_auth();
-        
-        // Generate unique job ID using simple approach
-        let mut counter: u32 = env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0);
-        counter += 1;
-        env.storage().instance().set(&Symbol::new(&env, "job_counter"), &counter);
+        storage::extend_instance_ttl(env);
+        Self::require_not_paused(env)?;
+        Self::require_current_schema(env)?;
 
-        // Create job ID from counter
-        let job_id_bytes = counter.to_be_bytes();
-        let mut job_id_array = [0u8; 32];
-        job_id_array[28..].copy_from_slice(&job_id_bytes);
-        let job_id = BytesN::from_array(&env, &job_id_array);
+        Self::validate_milestones(total_amount, &specs)?;
+        if review_period == 0 {
+            return Err(Error::InvalidReviewPeriod);
+        }
+        let strategy = Self::resolve_strategy(env, &asset_address, strategy)?;
+
+        let milestones = Self::new_milestones(env, &specs);
+
+        Ok(Self::fund_job(
+            env,
+            client,
+            freelancer,
+            total_amount,
+            asset_address,
+            milestones,
+            review_period,
+            strategy,
+        ))
+    }
+
+    /// Milestone terms splitting `total_amount` equally over `milestone_count` milestones
+    fn equal_milestones(env: &Env, total_amount: i128, milestone_count: u32) -> Result<Vec<MilestoneSpec>, Error> {
+        if milestone_count == 0 {
+            return Err(Error::NoMilestones);
+        }
 
-        // Create milestones
-        let mut milestones = Vec::<Milestone>::new(&env);
         let milestone_amount = total_amount / milestone_count as i128;
-        
+        let remainder = total_amount % milestone_count as i128;
+
+        let mut specs = Vec::<MilestoneSpec>::new(env);
         for i in 0..milestone_count {
+            let amount = if i + 1 == milestone_count {
+                milestone_amount + remainder
+            } else {
+                milestone_amount
+            };
+            specs.push_back(MilestoneSpec {
+                amount,
+                title_hash: BytesN::from_array(env, &[0u8; 32]),
+                due_date: 0,
+            });
+        }
+        Ok(specs)
+    }
+
+    /// The value of a `JobOptions` setting held as a vector of at most one entry
+    fn single_option<T>(values: Vec<T>) -> Result<Option<T>, Error>
+    where
+        T: IntoVal<Env, Val> + TryFromVal<Env, Val>,
+    {
+        match values.len() {
+            0 => Ok(None),
+            1 => Ok(values.get(0)),
+            _ => Err(Error::InvalidJobOptions),
+        }
+    }
+
+    /// Create the milestones of a new job from the client's terms
+    fn new_milestones(env: &Env, specs: &Vec<MilestoneSpec>) -> Vec<Milestone> {
+        let mut milestones = Vec::<Milestone>::new(env);
+        for (i, spec) in specs.iter().enumerate() {
             milestones.push_back(Milestone {
-                milestone_id: i + 1,
-                amount: milestone_amount,
-                proof_url: String::from_str(&env, ""),
+                milestone_id: i as u32 + 1,
+                amount: spec.amount,
+                title_hash: spec.title_hash,
+                due_date: spec.due_date,
+                proof_url: String::from_str(env, ""),
+                submitted_at: 0,
                 status: MilestoneStatus::Pending,
             });
         }
+        milestones
+    }
+
+    /// Record a new job under the next job id, move the client's funds into its yield
+    /// strategy and credit the job's ledger. The caller has validated the terms.
+    #[allow(clippy::too_many_arguments)]
+    fn fund_job(
+        env: &Env,
+        client: Address,
+        freelancer: Address,
+        total_amount: i128,
+        asset_address: Address,
+        milestones: Vec<Milestone>,
+        review_period: u64,
+        strategy: YieldStrategy,
+    ) -> BytesN<32> {
+        // Generate unique job ID using simple approach
+        let mut counter: u32 = env.storage().instance().get(&Symbol::new(env, "job_counter")).unwrap_or(0);
+        counter += 1;
+        env.storage().instance().set(&Symbol::new(env, "job_counter"), &counter);
+
+        // Create job ID from counter
+        let job_id_bytes = counter.to_be_bytes();
+        let mut job_id_array = [0u8; 32];
+        job_id_array[28..].copy_from_slice(&job_id_bytes);
+        let job_id = BytesN::from_array(env, &job_id_array);
+
+        // A named freelancer must accept the job before it starts; a posted job has
+        // the client standing in until one is hired
+        let now = env.ledger().timestamp();
+        let (status, accept_by) = if freelancer == client {
+            (JobStatus::Active, 0)
+        } else {
+            (JobStatus::Pending, now + DEFAULT_ACCEPTANCE_PERIOD)
+        };
 
         // Create job
         let job = Job {
@@ -115,240 +687,302 @@ impl EscrowCore {
             total_amount,
             asset_address: asset_address.clone(),
             milestones,
-            status: JobStatus::Active,
-            created_at: env.ledger().timestamp(),
+            status,
+            review_period,
+            created_at: now,
             yield_earned: 0,
+            strategy,
+            fee_bps: Self::fee_rate_for(env, &client),
+            terms_hash: BytesN::from_array(env, &[0u8; 32]),
+            accept_by,
         };
 
-        // Store job
-        env.storage().instance().set(&job_id, &job);
-
-        // Transfer funds directly from client to YieldHarvester using invoke_contract
-        let yield_harvester = Self::get_yield_harvester(&env);
-        
-        // Use invoke_contract to transfer tokens from client to yield_harvester
-        let mut transfer_args = Vec::new(&env);
-        transfer_args.push_back(client.clone().into_val(&env));
-        transfer_args.push_back(yield_harvester.clone().into_val(&env));
-        transfer_args.push_back(total_amount.into_val(&env));
-        env.invoke_contract::<()>(
-            &asset_address,
-            &Symbol::new(&env, "transfer"),
-            transfer_args,
-        );
-        
-        // Track deposit in YieldHarvester
-        let mut args = Vec::new(&env);
-        args.push_back(env.current_contract_address().into_val(&env));
-        args.push_back(total_amount.into_val(&env));
-        args.push_back(asset_address.clone().into_val(&env));
-        env.invoke_contract::<()>(
-            &yield_harvester,
-            &Symbol::new(&env, "track_deposit"),
-            args,
-        );
-
-        // Store job principal tracking
-        let principal_key = Symbol::new(&env, "principal_key");
-        env.storage().instance().set(&principal_key, &total_amount);
+        // Store job and list it for both parties; a posted job is listed for its
+        // freelancer once one is hired
+        storage::write_job(env, &job);
+        storage::index_client_job(env, &client, &job_id);
+        if freelancer != client {
+            storage::index_freelancer_job(env, &freelancer, &job_id);
+        }
 
-        job_id
-    }
+        // Transfer funds directly from client to the job's strategy
+        Self::deposit_principal(env, &job, &client, total_amount);
 
-    /// Get job details
-    pub fn get_job(env: Env, job_id: BytesN<32>) -> Job {
-        env.storage().instance().get(&job_id).unwrap_or_else(|| panic!("job not found"))
-    }
+        // Record the deposit in this job's ledger
+        let mut ledger = storage::read_ledger(env, &job_id);
+        ledger.funded += total_amount;
+        storage::write_ledger(env, &job_id, &ledger);
+        Self::adjust_total_escrowed(env, total_amount);
 
-    /// Get current job counter (number of jobs created)
-    pub fn get_job_counter(env: Env) -> u32 {
-        env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0)
+        job_id
     }
 
-    /// Get all jobs for a client
-    pub fn get_client_jobs(env: Env, client: Address) -> Vec<Job> {
-        let mut jobs = Vec::<Job>::new(&env);
-        let counter: u32 = env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0);
-
-        for i in 1..=counter {
-            let job_id_bytes = i.to_be_bytes();
-            let mut job_id_array = [0u8; 32];
-            job_id_array[28..].copy_from_slice(&job_id_bytes);
-            let job_id = BytesN::from_array(&env, &job_id_array);
+    /// Check that milestone terms are positive and add up to the job total
+    fn validate_milestones(total_amount: i128, specs: &Vec<MilestoneSpec>) -> Result<(), Error> {
+        if specs.is_empty() {
+            return Err(Error::NoMilestones);
+        }
 
-            if let Some(job) = env.storage().instance().get::<_, Job>(&job_id) {
-                if job.client == client {
-                    jobs.push_back(job);
-                }
+        let mut sum = 0i128;
+        for spec in specs.iter() {
+            if spec.amount <= 0 {
+                return Err(Error::InvalidMilestoneAmount);
             }
+            sum += spec.amount;
         }
 
-        jobs
-    }
-
-    /// Get all jobs for a freelancer
-    pub fn get_freelancer_jobs(env: Env, freelancer: Address) -> Vec<Job> {
-        let mut jobs = Vec::<Job>::new(&env);
-        let counter: u32 = env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0);
-
-        for i in 1..=counter {
-            let job_id_bytes = i.to_be_bytes();
-            let mut job_id_array = [0u8; 32];
-            job_id_array[28..].copy_from_slice(&job_id_bytes);
-            let job_id = BytesN::from_array(&env, &job_id_array);
-
-            if let Some(job) = env.storage().instance().get::<_, Job>(&job_id) {
-                if job.freelancer == freelancer {
-                    jobs.push_back(job);
-                }
-            }
+        if sum != total_amount {
+            return Err(Error::MilestoneTotalMismatch);
         }
 
-        jobs
+        Ok(())
     }
 
-    /// Approve milestone
-    pub fn approve_milestone(env: Env, job_id: BytesN<32>, milestone_id: u32) {
-        let mut job = Self::get_job(env.clone(), job_id.clone());
-        
+    /// Client releases an approved milestone, optionally converting the payout.
+    /// Returns the amount delivered to the freelancer.
+    fn release_approved(
+        env: &Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        conversion: Option<Conversion>,
+    ) -> Result<i128, Error> {
+        let mut job = Self::get_job(env.clone(), job_id)?;
+
         // Require client authorization
         job.client.require_auth();
-        
-        // Find and update milestone
-        let mut milestone_found = false;
-        for i in 0..job.milestones.len() {
-            let mut milestone = job.milestones.get(i).unwrap();
-            if milestone.milestone_id == milestone_id {
-                milestone.status = MilestoneStatus::Approved;
-                job.milestones.set(i, milestone);
-                milestone_found = true;
-                break;
-            }
+        storage::extend_instance_ttl(env);
+        Self::require_no_organization(env, &job)?;
+
+        Self::release(env, &mut job, milestone_id, conversion)
+    }
+
+    /// Approve a milestone whose proof was submitted and store the job.
+    /// The caller has checked authorization.
+    fn approve(env: &Env, job: &mut Job, milestone_id: u32) -> Result<(), Error> {
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
         }
 
-        if !milestone_found {
-            panic!("milestone not found");
+        // Find and update milestone
+        let (index, mut milestone) = Self::find_milestone(job, milestone_id)?;
+        if milestone.status != MilestoneStatus::ProofSubmitted {
+            return Err(Error::ProofNotSubmitted);
         }
+        milestone.status = MilestoneStatus::Approved;
+        job.milestones.set(index, milestone);
 
         // Update job
-        env.storage().instance().set(&job_id, &job);
+        storage::write_job(env, job);
+
+        Ok(())
     }
 
-    /// Release payment for milestone
-    pub fn release_payment(
-        env: Env,
-        job_id: BytesN<32>,
-        milestone_id: u32,
-    ) {
-        let mut job = Self::get_job(env.clone(), job_id.clone());
-        
-        // Require client authorization
-        job.client.require_auth();
-        
-        // Find milestone
-        let mut milestone_amount = 0i128;
-        let mut milestone_found = false;
-        
-        for i in 0..job.milestones.len() {
-            let mut milestone = job.milestones.get(i).unwrap();
-            if milestone.milestone_id == milestone_id {
-                if milestone.status != MilestoneStatus::Approved {
-                    panic!("milestone not approved");
-                }
-                milestone_amount = milestone.amount;
-                milestone.status = MilestoneStatus::Paid;
-                job.milestones.set(i, milestone);
-                milestone_found = true;
-                break;
-            }
+    /// Pay an approved milestone and store the job. The caller has checked authorization.
+    /// Returns the amount delivered to the freelancer.
+    fn release(env: &Env, job: &mut Job, milestone_id: u32, conversion: Option<Conversion>) -> Result<i128, Error> {
+        // Releases are frozen while a dispute is open
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
         }
 
-        if !milestone_found {
-            panic!("milestone not found");
+        // Find milestone
+        let (index, milestone) = Self::find_milestone(job, milestone_id)?;
+        if milestone.status != MilestoneStatus::Approved {
+            return Err(Error::MilestoneNotApproved);
         }
 
-        // Withdraw from YieldHarvester and send directly to freelancer
-        let yield_harvester = Self::get_yield_harvester(&env);
-        let mut args = Vec::new(&env);
-        // owner (client) initiates withdrawal of their deposit
-        args.push_back(job.client.clone().into_val(&env));
-        // principal amount to pay out for this milestone
-        args.push_back(milestone_amount.into_val(&env));
-        // principal recipient: freelancer
-        args.push_back(job.freelancer.clone().into_val(&env));
-        // yield recipient: client
-        args.push_back(job.client.clone().into_val(&env));
+        let delivered = Self::pay_milestone(env, job, index, conversion)?;
+        storage::write_job(env, job);
 
-        let _total_withdrawn = env.invoke_contract::<i128>(
-            &yield_harvester,
-            &Symbol::new(&env, "withdraw_to"),
-            args,
-        );
+        Ok(delivered)
+    }
 
-        // Update principal tracking
-        let principal_key = Symbol::new(&env, "principal_key");
-        let current_principal: i128 = env.storage().instance().get(&principal_key).unwrap_or(0);
-        let new_principal = current_principal - milestone_amount;
-        env.storage().instance().set(&principal_key, &new_principal);
+    /// Pay a milestone's principal out of escrow to the freelancer, mark it paid and
+    /// complete the job once every milestone is settled. The caller stores the job.
+    /// Returns the amount delivered to the freelancer.
+    fn pay_milestone(env: &Env, job: &mut Job, index: u32, conversion: Option<Conversion>) -> Result<i128, Error> {
+        Self::require_not_paused(env)?;
 
-        // Update job
-        env.storage().instance().set(&job_id, &job);
+        let mut milestone = job.milestones.get(index).unwrap();
+        let milestone_amount = milestone.amount;
+
+        let mut ledger = storage::read_ledger(env, &job.job_id);
+        if ledger.balance() < milestone_amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        // Withdraw from the job's strategy and send directly to freelancer, less the fee
+        let (yield_amount, delivered) =
+            Self::pay_freelancer(env, job, milestone.milestone_id, milestone_amount, conversion)?;
+
+        // Update this job's ledger
+        ledger.released += milestone_amount;
+        ledger.yield_attributed += yield_amount;
+        job.yield_earned += yield_amount;
+        storage::write_ledger(env, &job.job_id, &ledger);
+        Self::adjust_total_escrowed(env, -milestone_amount);
+
+        milestone.status = MilestoneStatus::Paid;
+        job.milestones.set(index, milestone);
 
         // Check if all milestones are paid
-        let all_paid = job.milestones.iter().all(|m| m.status == MilestoneStatus::Paid);
-        if all_paid {
+        if Self::all_milestones_settled(env, job) {
             job.status = JobStatus::Completed;
-            env.storage().instance().set(&job_id, &job);
         }
+
+        Ok(delivered)
     }
 
-    /// Get YieldHarvester address
-    fn get_yield_harvester(env: &Env) -> Address {
-        env.storage().instance()
-            .get(&Symbol::new(env, "yield_harvester"))
-            .unwrap()
+    /// Whether every milestone of a job has been settled.
+    /// Hourly jobs stay open for new time logs until the client closes them.
+    fn all_milestones_settled(env: &Env, job: &Job) -> bool {
+        storage::read_hourly_terms(env, &job.job_id).is_none()
+            && job.milestones.iter().all(|m| m.status.is_settled())
     }
 
-    /// Get LiquidityRouter address
-    fn get_liquidity_router(env: &Env) -> Address {
-        env.storage().instance()
-            .get(&Symbol::new(env, "liquidity_router"))
-            .unwrap()
+    /// Id for a milestone added to a job. Ids are never reused, even after the
+    /// milestone holding the highest one is removed.
+    fn next_milestone_id(env: &Env, job: &Job) -> u32 {
+        let milestone_id = Self::last_milestone_id(env, job) + 1;
+        storage::write_milestone_counter(env, &job.job_id, milestone_id);
+        milestone_id
     }
 
-    /// Get USDC token address
-    fn get_usdc_token(env: &Env) -> Address {
-        env.storage().instance()
-            .get(&Symbol::new(env, "usdc_token"))
-            .unwrap()
+    /// Highest milestone id a job has handed out. Jobs count from their last
+    /// milestone until an id is first recorded.
+    fn last_milestone_id(env: &Env, job: &Job) -> u32 {
+        storage::read_milestone_counter(env, &job.job_id)
+            .unwrap_or_else(|| job.milestones.last().map(|m| m.milestone_id).unwrap_or(0))
+    }
+
+    /// Load the jobs behind a page of job ids
+    fn read_jobs(env: &Env, job_ids: Vec<BytesN<32>>) -> Vec<Job> {
+        let mut jobs = Vec::<Job>::new(env);
+        for job_id in job_ids.iter() {
+            if let Some(job) = storage::read_job(env, &job_id) {
+                jobs.push_back(job);
+            }
+        }
+        jobs
     }
 
-    /// Cancel job and refund client
-    pub fn cancel_job(env: Env, job_id: BytesN<32>) {
-        let mut job = Self::get_job(env.clone(), job_id.clone());
+    /// Find a milestone and its position in the job's milestone list
+    fn find_milestone(job: &Job, milestone_id: u32) -> Result<(u32, Milestone), Error> {
+        for i in 0..job.milestones.len() {
+            let milestone = job.milestones.get(i).unwrap();
+            if milestone.milestone_id == milestone_id {
+                return Ok((i, milestone));
+            }
+        }
+        Err(Error::MilestoneNotFound)
+    }
+
+    /// Adjust the contract-wide escrowed principal
+    fn adjust_total_escrowed(env: &Env, delta: i128) {
+        let key = Symbol::new(env, "total_escrowed");
+        let total: i128 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(total + delta));
+    }
+
+    /// Refund `amount` of the job's escrow to the client and share out its yield
+    pub(crate) fn refund_client(env: &Env, job: &mut Job, ledger: &mut JobLedger, amount: i128) -> Result<(), Error> {
+        if amount <= 0 {
+            return Ok(());
+        }
+        if ledger.balance() < amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        let yield_amount = Self::withdraw_principal(env, job, amount, &job.client)?;
+        ledger.refunded += amount;
+        ledger.yield_attributed += yield_amount;
+        job.yield_earned += yield_amount;
+        Self::adjust_total_escrowed(env, -amount);
+
+        Ok(())
+    }
+
+    /// Cancel job (or an open posting) and refund client
+    pub fn cancel_job(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
         
         // Require client authorization
         job.client.require_auth();
+        storage::extend_instance_ttl(&env);
         
+        Self::cancel(&env, &mut job)
+    }
+
+    /// Send submitted proof back to the freelancer and store the job.
+    /// The caller has checked authorization.
+    fn reject(env: &Env, job: &mut Job, milestone_id: u32) -> Result<(), Error> {
         if job.status != JobStatus::Active {
-            panic!("job not active");
+            return Err(Error::JobNotActive);
+        }
+
+        let (index, mut milestone) = Self::find_milestone(job, milestone_id)?;
+        if milestone.status != MilestoneStatus::ProofSubmitted {
+            return Err(Error::ProofNotSubmitted);
+        }
+        milestone.status = MilestoneStatus::Pending;
+        job.milestones.set(index, milestone);
+        storage::write_job(env, job);
+
+        // Approvals collected for the rejected proof do not carry over
+        storage::remove_approvals(env, &job.job_id, milestone_id, &OrgAction::Approve);
+
+        let revision = storage::read_proofs(env, &job.job_id, milestone_id)
+            .last()
+            .map(|p| p.revision)
+            .unwrap_or(0);
+        events::MilestoneRejected {
+            job_id: job.job_id.clone(),
+            milestone_id,
+            revision,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Cancel a job, refund the client and store the job. The caller has checked
+    /// authorization.
+    fn cancel(env: &Env, job: &mut Job) -> Result<(), Error> {
+        if job.status != JobStatus::Active && job.status != JobStatus::Open {
+            return Err(Error::JobNotActive);
         }
 
-        // Calculate refund amount
-        let principal_key = Symbol::new(&env, "principal_key");
-        let refund_amount: i128 = env.storage().instance().get(&principal_key).unwrap_or(0);
+        // Streamed funds already vested stay with the freelancer
+        if let Some(stream) = storage::read_stream(env, &job.job_id) {
+            return Self::stop(env, job, stream);
+        }
 
-        // Refund to client
-        if refund_amount > 0 {
-            let token_client = token::TokenClient::new(&env, &job.asset_address);
-            token_client.transfer(&env.current_contract_address(), &job.client, &refund_amount);
+        // Hourly jobs close out their time logs the same way `close_hourly_job` does
+        if storage::read_hourly_terms(env, &job.job_id).is_some() {
+            Self::expire_time_logs(job)?;
         }
 
+        // Work handed in for review must be settled first, or the client could cancel
+        // as soon as proof arrives and keep the freelancer from claiming it
+        if job.milestones.iter().any(|m| {
+            m.status == MilestoneStatus::ProofSubmitted || m.status == MilestoneStatus::Approved
+        }) {
+            return Err(Error::MilestonesInReview);
+        }
+
+        // Refund whatever principal this job still holds in its strategy
+        let mut ledger = storage::read_ledger(env, &job.job_id);
+        let refund_amount = ledger.balance();
+
+        Self::refund_client(env, job, &mut ledger, refund_amount)?;
+        storage::write_ledger(env, &job.job_id, &ledger);
+
         // Update job status
         job.status = JobStatus::Cancelled;
-        env.storage().instance().set(&job_id, &job);
+        storage::write_job(env, job);
 
-        // Clear principal tracking
-        env.storage().instance().remove(&principal_key);
+        Ok(())
     }
 }
+
+mod test;
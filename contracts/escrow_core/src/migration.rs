@@ -0,0 +1,323 @@
+// Storage schema versioning for escrow_core
+// The schema version is kept in instance storage. Contracts initialized with this
+// code start at `SCHEMA_VERSION`; a contract upgraded from an older layout runs
+// `migrate` in batches until every stored job has been rewritten. Version 1 contracts
+// had no admin: the first `migrate` call must be signed by `LEGACY_ADMIN`, the
+// deployer address baked in when the upgrade is built, and stores it as the admin.
+// Jobs cannot be read or changed while a migration is pending.
+//
+// Version 1: jobs stored in instance storage under their raw id, milestones without
+//            terms, one global `principal_key` for escrowed principal.
+// Version 2: jobs, ledgers and proofs in persistent storage under `DataKey`.
+// Version 3: jobs carry the fee rate snapshotted at creation.
+// Version 4: jobs carry the hash of the terms the freelancer accepted and the
+//            deadline for accepting them.
+// Version 5: jobs are listed per client and per freelancer.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec};
+
+use crate::{
+    events, storage, storage::DataKey, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job,
+    JobLedger, JobStatus, LegacyHarvesterClient, Milestone, MilestoneStatus, YieldStrategy,
+    DEFAULT_REVIEW_PERIOD,
+};
+
+/// Schema version written by this code
+pub const SCHEMA_VERSION: u32 = 5;
+
+/// Version assumed when none is stored (contracts deployed before versioning)
+const LEGACY_SCHEMA_VERSION: u32 = 1;
+
+/// Admin of version 1 contracts, set from `ESCROW_LEGACY_ADMIN` when building the
+/// upgrade. Without it, version 1 contracts cannot be migrated.
+#[cfg(not(test))]
+const LEGACY_ADMIN: Option<&str> = option_env!("ESCROW_LEGACY_ADMIN");
+#[cfg(test)]
+pub(crate) const LEGACY_ADMIN: Option<&str> = Some("GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF");
+
+#[contracttype]
+#[derive(Clone)]
+pub struct MilestoneV1 {
+    pub milestone_id: u32,
+    pub amount: i128,
+    pub proof_url: String,
+    pub status: MilestoneStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct JobV1 {
+    pub job_id: BytesN<32>,
+    pub client: Address,
+    pub freelancer: Address,
+    pub total_amount: i128,
+    pub asset_address: Address,
+    pub milestones: Vec<MilestoneV1>,
+    pub status: JobStatus,
+    pub created_at: u64,
+    pub yield_earned: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct JobV2 {
+    pub job_id: BytesN<32>,
+    pub client: Address,
+    pub freelancer: Address,
+    pub total_amount: i128,
+    pub asset_address: Address,
+    pub milestones: Vec<Milestone>,
+    pub status: JobStatus,
+    pub review_period: u64,
+    pub created_at: u64,
+    pub yield_earned: i128,
+    pub strategy: YieldStrategy,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct JobV3 {
+    pub job_id: BytesN<32>,
+    pub client: Address,
+    pub freelancer: Address,
+    pub total_amount: i128,
+    pub asset_address: Address,
+    pub milestones: Vec<Milestone>,
+    pub status: JobStatus,
+    pub review_period: u64,
+    pub created_at: u64,
+    pub yield_earned: i128,
+    pub strategy: YieldStrategy,
+    pub fee_bps: u32,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Get the storage schema version of this contract
+    pub fn get_schema_version(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "schema_version"))
+            .unwrap_or(LEGACY_SCHEMA_VERSION)
+    }
+
+    /// Admin: rewrite up to `batch_size` stored jobs into the current layout.
+    /// Returns how many job ids are left to scan; call again until it returns 0.
+    pub fn migrate(env: Env, batch_size: u32) -> Result<u32, Error> {
+        match Self::read_admin(&env) {
+            Ok(admin) => admin.require_auth(),
+            Err(Error::NotInitialized) => Self::set_legacy_admin(&env)?,
+            Err(err) => return Err(err),
+        }
+        storage::extend_instance_ttl(&env);
+
+        let from_version = Self::get_schema_version(env.clone());
+        if from_version >= SCHEMA_VERSION {
+            return Ok(0);
+        }
+
+        let cursor_key = Symbol::new(&env, "migration_cursor");
+        let counter: u32 = env.storage().instance().get(&Symbol::new(&env, "job_counter")).unwrap_or(0);
+        let mut cursor: u32 = env.storage().instance().get(&cursor_key).unwrap_or(1);
+
+        let end = cursor.saturating_add(batch_size).min(counter + 1);
+        while cursor < end {
+            let job_id_bytes = cursor.to_be_bytes();
+            let mut job_id_array = [0u8; 32];
+            job_id_array[28..].copy_from_slice(&job_id_bytes);
+            let job_id = BytesN::from_array(&env, &job_id_array);
+
+            let job = if from_version >= 4 {
+                storage::read_job(&env, &job_id)
+            } else if from_version < 2 {
+                match env.storage().instance().get::<_, JobV1>(&job_id) {
+                    Some(legacy) => {
+                        env.storage().instance().remove(&job_id);
+                        let job = Self::upgrade_job_v2(Self::upgrade_job_v1(&env, legacy)?);
+                        Some(Self::upgrade_job_v3(&env, job))
+                    }
+                    None => None,
+                }
+            } else if from_version < 3 {
+                env.storage()
+                    .persistent()
+                    .get::<_, JobV2>(&DataKey::Job(job_id))
+                    .map(|job| Self::upgrade_job_v3(&env, Self::upgrade_job_v2(job)))
+            } else {
+                env.storage()
+                    .persistent()
+                    .get::<_, JobV3>(&DataKey::Job(job_id))
+                    .map(|job| Self::upgrade_job_v3(&env, job))
+            };
+
+            if let Some(job) = job {
+                storage::write_job(&env, &job);
+                storage::index_client_job(&env, &job.client, &job.job_id);
+                if job.freelancer != job.client {
+                    storage::index_freelancer_job(&env, &job.freelancer, &job.job_id);
+                }
+            }
+            cursor += 1;
+        }
+
+        if cursor <= counter {
+            env.storage().instance().set(&cursor_key, &cursor);
+            return Ok(counter + 1 - cursor);
+        }
+
+        env.storage().instance().remove(&cursor_key);
+        env.storage().instance().remove(&Symbol::new(&env, "principal_key"));
+        env.storage().instance().set(&Symbol::new(&env, "schema_version"), &SCHEMA_VERSION);
+
+        events::SchemaMigrated {
+            from_version,
+            to_version: SCHEMA_VERSION,
+        }
+        .publish(&env);
+
+        Ok(0)
+    }
+}
+
+impl EscrowCore {
+    /// Store `LEGACY_ADMIN` as the admin of a version 1 contract, with its signature
+    fn set_legacy_admin(env: &Env) -> Result<(), Error> {
+        if !env.storage().instance().has(&Symbol::new(env, "init"))
+            || Self::get_schema_version(env.clone()) != LEGACY_SCHEMA_VERSION
+        {
+            return Err(Error::NotInitialized);
+        }
+        let admin = Address::from_str(env, LEGACY_ADMIN.ok_or(Error::NotInitialized)?);
+        admin.require_auth();
+
+        env.storage().instance().set(&Symbol::new(env, "admin"), &admin);
+
+        events::AdminSet { admin }.publish(env);
+
+        Ok(())
+    }
+
+    /// Fail while stored jobs still have to be migrated to the current layout
+    pub(crate) fn require_current_schema(env: &Env) -> Result<(), Error> {
+        if Self::get_schema_version(env.clone()) < SCHEMA_VERSION {
+            return Err(Error::MigrationPending);
+        }
+        Ok(())
+    }
+
+    /// Convert a version 1 job: milestones get empty terms and the remainder of the
+    /// equal split is moved to the last milestone. The principal still held for the
+    /// job is moved into a YieldHarvester strategy position and the job's ledger is
+    /// written.
+    fn upgrade_job_v1(env: &Env, legacy: JobV1) -> Result<JobV2, Error> {
+        let mut milestones = Vec::<Milestone>::new(env);
+        let mut allocated = 0i128;
+        let mut released = 0i128;
+        let count = legacy.milestones.len();
+        for (i, old) in legacy.milestones.iter().enumerate() {
+            let amount = if i as u32 + 1 == count {
+                legacy.total_amount - allocated
+            } else {
+                old.amount
+            };
+            allocated += amount;
+            if old.status == MilestoneStatus::Paid {
+                released += amount;
+            }
+
+            milestones.push_back(Milestone {
+                milestone_id: old.milestone_id,
+                amount,
+                title_hash: BytesN::from_array(env, &[0u8; 32]),
+                due_date: 0,
+                proof_url: old.proof_url,
+                submitted_at: 0,
+                status: old.status,
+            });
+        }
+
+        let yield_harvester = Self::get_yield_harvester(env)?;
+        let mut job = JobV2 {
+            job_id: legacy.job_id.clone(),
+            client: legacy.client,
+            freelancer: legacy.freelancer,
+            total_amount: legacy.total_amount,
+            asset_address: legacy.asset_address.clone(),
+            milestones,
+            status: legacy.status,
+            review_period: DEFAULT_REVIEW_PERIOD,
+            created_at: legacy.created_at,
+            yield_earned: legacy.yield_earned,
+            strategy: YieldStrategy::Contract(yield_harvester.clone()),
+        };
+
+        // Cancelled version 1 jobs were refunded in full
+        let ledger = JobLedger {
+            funded: legacy.total_amount,
+            released,
+            refunded: if job.status == JobStatus::Cancelled {
+                legacy.total_amount - released
+            } else {
+                0
+            },
+            yield_attributed: 0,
+        };
+
+        // The principal comes back out of the legacy position, paying its yield to the
+        // client as version 1 releases did, and goes in again as a strategy deposit
+        let outstanding = ledger.balance();
+        if outstanding > 0 {
+            let withdrawn = LegacyHarvesterClient::new(env, &yield_harvester).withdraw_to(
+                &env.current_contract_address(),
+                &outstanding,
+                &env.current_contract_address(),
+                &job.client,
+            );
+            job.yield_earned += withdrawn - outstanding;
+            Self::deposit_to_strategy(env, &yield_harvester, &legacy.asset_address, &job.job_id, 0, outstanding);
+            Self::adjust_total_escrowed(env, outstanding);
+        }
+
+        storage::write_ledger(env, &job.job_id, &ledger);
+
+        Ok(job)
+    }
+
+    /// Convert a version 2 job. Jobs created before fees existed pay none.
+    fn upgrade_job_v2(job: JobV2) -> JobV3 {
+        JobV3 {
+            job_id: job.job_id,
+            client: job.client,
+            freelancer: job.freelancer,
+            total_amount: job.total_amount,
+            asset_address: job.asset_address,
+            milestones: job.milestones,
+            status: job.status,
+            review_period: job.review_period,
+            created_at: job.created_at,
+            yield_earned: job.yield_earned,
+            strategy: job.strategy,
+            fee_bps: 0,
+        }
+    }
+
+    /// Convert a version 3 job. Jobs created before acceptance existed have no terms
+    /// on record.
+    fn upgrade_job_v3(env: &Env, job: JobV3) -> Job {
+        Job {
+            job_id: job.job_id,
+            client: job.client,
+            freelancer: job.freelancer,
+            total_amount: job.total_amount,
+            asset_address: job.asset_address,
+            milestones: job.milestones,
+            status: job.status,
+            review_period: job.review_period,
+            created_at: job.created_at,
+            yield_earned: job.yield_earned,
+            strategy: job.strategy,
+            fee_bps: job.fee_bps,
+            terms_hash: BytesN::from_array(env, &[0u8; 32]),
+            accept_by: 0,
+        }
+    }
+}
@@ -0,0 +1,219 @@
+// Organization approvals for escrow_core
+// A client account can be run by an organization: a member list and a threshold. Jobs
+// paid from such an account are approved and released only once `threshold` distinct
+// members have signed the action; the account can no longer do either on its own.
+// Change orders on its jobs likewise need `threshold` members signing with the client side.
+// Signatures are kept per milestone and action until the threshold is reached, and
+// members can revoke theirs before then. Once set, the organization itself can only be
+// changed or cleared with `threshold` of its members signing alongside the account.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus,
+    MilestoneStatus,
+};
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub enum OrgAction {
+    Approve,
+    Release,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Organization {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Require `threshold` of `members` to approve and release milestones on jobs
+    /// paid by `account`. Replacing an existing organization needs `signers` to
+    /// include `threshold` of its current members.
+    pub fn set_organization(
+        env: Env,
+        account: Address,
+        members: Vec<Address>,
+        threshold: u32,
+        signers: Vec<Address>,
+    ) -> Result<(), Error> {
+        account.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_organization_signers(&env, &account, &signers)?;
+
+        if threshold == 0 || threshold > members.len() {
+            return Err(Error::InvalidOrganization);
+        }
+        for (i, member) in members.iter().enumerate() {
+            if members.first_index_of(&member) != Some(i as u32) {
+                return Err(Error::InvalidOrganization);
+            }
+        }
+
+        storage::write_organization(&env, &account, &Organization { members, threshold });
+
+        Ok(())
+    }
+
+    /// Let `account` approve and release milestones on its own again, with `signers`
+    /// including `threshold` of the organization's members
+    pub fn clear_organization(env: Env, account: Address, signers: Vec<Address>) -> Result<(), Error> {
+        account.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_organization_signers(&env, &account, &signers)?;
+
+        storage::remove_organization(&env, &account);
+
+        Ok(())
+    }
+
+    /// Get the organization that signs for a client account, if any
+    pub fn get_organization(env: Env, account: Address) -> Option<Organization> {
+        storage::read_organization(&env, &account)
+    }
+
+    /// Member of the client's organization signs an action on a milestone. The action
+    /// is carried out with the signature that reaches the threshold.
+    /// Returns whether it was carried out.
+    pub fn sign_approval(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        member: Address,
+        action: OrgAction,
+    ) -> Result<bool, Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let organization = Self::client_organization(&env, &job, &member)?;
+
+        member.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        // Only collect signatures for an action that can still be taken
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+        let (_, milestone) = Self::find_milestone(&job, milestone_id)?;
+        match action {
+            OrgAction::Approve if milestone.status != MilestoneStatus::ProofSubmitted => {
+                return Err(Error::ProofNotSubmitted);
+            }
+            OrgAction::Release if milestone.status != MilestoneStatus::Approved => {
+                return Err(Error::MilestoneNotApproved);
+            }
+            _ => {}
+        }
+
+        let mut approvals = storage::read_approvals(&env, &job_id, milestone_id, &action);
+        if approvals.contains(&member) {
+            return Err(Error::AlreadySigned);
+        }
+        approvals.push_back(member.clone());
+
+        // Signatures of members removed since they signed do not count
+        let count = approvals
+            .iter()
+            .filter(|signer| organization.members.contains(signer))
+            .count() as u32;
+
+        events::ApprovalSigned {
+            job_id: job_id.clone(),
+            milestone_id,
+            action: action.clone(),
+            member,
+            approvals: count,
+            threshold: organization.threshold,
+        }
+        .publish(&env);
+
+        if count < organization.threshold {
+            storage::write_approvals(&env, &job_id, milestone_id, &action, &approvals);
+            return Ok(false);
+        }
+
+        storage::remove_approvals(&env, &job_id, milestone_id, &action);
+        match action {
+            OrgAction::Approve => Self::approve(&env, &mut job, milestone_id)?,
+            OrgAction::Release => {
+                Self::release(&env, &mut job, milestone_id, None)?;
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Member withdraws their signature from an action that has not been carried out yet
+    pub fn revoke_approval(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        member: Address,
+        action: OrgAction,
+    ) -> Result<(), Error> {
+        member.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut approvals = storage::read_approvals(&env, &job_id, milestone_id, &action);
+        let index = approvals.first_index_of(&member).ok_or(Error::NotSigned)?;
+        approvals.remove(index);
+        storage::write_approvals(&env, &job_id, milestone_id, &action, &approvals);
+
+        events::ApprovalRevoked {
+            job_id,
+            milestone_id,
+            action,
+            member,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the members who have signed an action on a milestone so far
+    pub fn get_approvals(env: Env, job_id: BytesN<32>, milestone_id: u32, action: OrgAction) -> Vec<Address> {
+        storage::read_approvals(&env, &job_id, milestone_id, &action)
+    }
+}
+
+impl EscrowCore {
+    /// Fail when the job's client must act through its organization
+    pub(crate) fn require_no_organization(env: &Env, job: &Job) -> Result<(), Error> {
+        if storage::read_organization(env, &job.client).is_some() {
+            return Err(Error::OrganizationApprovalRequired);
+        }
+        Ok(())
+    }
+
+    /// Require `threshold` distinct members of `account`'s current organization, if it
+    /// has one, to authorize as `signers`
+    pub(crate) fn require_organization_signers(env: &Env, account: &Address, signers: &Vec<Address>) -> Result<(), Error> {
+        let organization = match storage::read_organization(env, account) {
+            Some(organization) => organization,
+            None => return Ok(()),
+        };
+
+        for (i, signer) in signers.iter().enumerate() {
+            if !organization.members.contains(&signer) {
+                return Err(Error::NotOrganizationMember);
+            }
+            if signers.first_index_of(&signer) != Some(i as u32) {
+                return Err(Error::AlreadySigned);
+            }
+            signer.require_auth();
+        }
+        if signers.len() < organization.threshold {
+            return Err(Error::OrganizationApprovalRequired);
+        }
+        Ok(())
+    }
+
+    /// The organization of the job's client, checking that `member` belongs to it
+    fn client_organization(env: &Env, job: &Job, member: &Address) -> Result<Organization, Error> {
+        let organization = storage::read_organization(env, &job.client).ok_or(Error::InvalidOrganization)?;
+        if !organization.members.contains(member) {
+            return Err(Error::NotOrganizationMember);
+        }
+        Ok(organization)
+    }
+}
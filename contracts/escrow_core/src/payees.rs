@@ -0,0 +1,174 @@
+// Milestone payees for escrow_core
+// The job's freelancer is its lead. A milestone can instead share its payout between
+// several payees in basis points, set by the client when the job is created and
+// amended later only with both the client's and the lead freelancer's consent.
+// Each payee is paid following their own payout profile.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Map, Vec};
+
+use crate::{
+    events, payout::Conversion, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job,
+    JobStatus, MilestoneStatus, BPS_DENOMINATOR,
+};
+
+/// Share of a milestone's payout sent to `recipient`, in basis points
+#[contracttype]
+#[derive(Clone)]
+pub struct Payee {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// Amount of the job's asset paid to one payee of a milestone, after the fee
+#[contracttype]
+#[derive(Clone)]
+pub struct PayeeAmount {
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Client and lead freelancer: replace the payees of a milestone that has not been
+    /// paid yet. An empty list pays the lead freelancer alone.
+    pub fn amend_milestone_payees(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        payees: Vec<Payee>,
+    ) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        // Both parties must consent
+        job.client.require_auth();
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let (_, milestone) = Self::find_milestone(&job, milestone_id)?;
+        if milestone.status.is_settled() || milestone.status == MilestoneStatus::Disputed {
+            return Err(Error::MilestoneNotAmendable);
+        }
+
+        if payees.is_empty() {
+            storage::remove_milestone_payees(&env, &job_id, milestone_id);
+        } else {
+            Self::validate_payees(&payees)?;
+            storage::write_milestone_payees(&env, &job_id, milestone_id, &payees);
+        }
+
+        events::MilestonePayeesAmended {
+            job_id,
+            milestone_id,
+            payees,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the payees of a milestone (the lead freelancer alone unless configured)
+    pub fn get_milestone_payees(env: Env, job_id: BytesN<32>, milestone_id: u32) -> Result<Vec<Payee>, Error> {
+        let job = Self::get_job(env.clone(), job_id)?;
+        Self::find_milestone(&job, milestone_id)?;
+
+        Ok(Self::milestone_payees(&env, &job, milestone_id))
+    }
+}
+
+impl EscrowCore {
+    fn milestone_payees(env: &Env, job: &Job, milestone_id: u32) -> Vec<Payee> {
+        storage::read_milestone_payees(env, &job.job_id, milestone_id).unwrap_or_else(|| {
+            Vec::from_array(
+                env,
+                [Payee {
+                    recipient: job.freelancer.clone(),
+                    bps: BPS_DENOMINATOR,
+                }],
+            )
+        })
+    }
+
+    /// Check the payees a new job with `milestone_count` milestones is created with
+    pub(crate) fn validate_job_payees(milestone_count: u32, payees: &Map<u32, Vec<Payee>>) -> Result<(), Error> {
+        for (milestone_id, milestone_payees) in payees.iter() {
+            if milestone_id == 0 || milestone_id > milestone_count {
+                return Err(Error::MilestoneNotFound);
+            }
+            Self::validate_payees(&milestone_payees)?;
+        }
+        Ok(())
+    }
+
+    /// Check that payee shares are positive and add up to the whole payout
+    fn validate_payees(payees: &Vec<Payee>) -> Result<(), Error> {
+        let mut total = 0u32;
+        for payee in payees.iter() {
+            if payee.bps == 0 {
+                return Err(Error::InvalidPayees);
+            }
+            total = total.saturating_add(payee.bps);
+        }
+        if total != BPS_DENOMINATOR {
+            return Err(Error::InvalidPayees);
+        }
+        Ok(())
+    }
+
+    /// Share `amount` of a milestone's payout between its payees, the last one taking
+    /// the rounding remainder. A requested minimum output is shared the same way.
+    /// Returns the yield shared out with it, the total delivered and each
+    /// payee's amount in the job's asset.
+    pub(crate) fn pay_payees(
+        env: &Env,
+        job: &Job,
+        milestone_id: u32,
+        amount: i128,
+        conversion: Option<Conversion>,
+    ) -> Result<(i128, i128, Vec<PayeeAmount>), Error> {
+        let payees = Self::milestone_payees(env, job, milestone_id);
+
+        let mut yield_amount = 0i128;
+        let mut delivered = 0i128;
+        let mut payouts = Vec::new(env);
+        let mut remaining = amount;
+        let mut remaining_min = conversion.as_ref().map(|c| c.min_amount_out).unwrap_or(0);
+        for (i, payee) in payees.iter().enumerate() {
+            let last = i as u32 + 1 == payees.len();
+            let share = if last {
+                remaining
+            } else {
+                amount * payee.bps as i128 / BPS_DENOMINATOR as i128
+            };
+            remaining -= share;
+
+            let payee_conversion = conversion.as_ref().map(|conversion| {
+                let min_amount_out = if last {
+                    remaining_min
+                } else {
+                    conversion.min_amount_out * payee.bps as i128 / BPS_DENOMINATOR as i128
+                };
+                remaining_min -= min_amount_out;
+                Conversion {
+                    min_amount_out,
+                    ..conversion.clone()
+                }
+            });
+
+            if share > 0 {
+                let (payee_yield, payee_delivered) =
+                    Self::deliver_payout(env, job, milestone_id, share, &payee.recipient, payee_conversion)?;
+                yield_amount += payee_yield;
+                delivered += payee_delivered;
+            }
+            payouts.push_back(PayeeAmount {
+                recipient: payee.recipient,
+                amount: share,
+            });
+        }
+
+        Ok((yield_amount, delivered, payouts))
+    }
+}
@@ -0,0 +1,290 @@
+// Freelancer payouts for escrow_core
+// A payout is sent in the job's asset, or converted through the LiquidityRouter when
+// the client asks for another asset on release or the freelancer's payout profile
+// prefers one. Conversions are bounded by a minimum output, checked against what was
+// actually received, and a deadline. A conversion the client asked for must succeed;
+// one a profile asks for falls back to the job's asset when the router cannot meet
+// its bound. A profile can also split every payout between several addresses.
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{contractimpl, contracttype, token, vec, Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job,
+    LiquidityRouterClient, BPS_DENOMINATOR, RATE_SCALE,
+};
+
+/// Conversion requested for a payout
+#[derive(Clone)]
+pub struct Conversion {
+    pub target_asset: Address,
+    pub min_amount_out: i128,
+    pub deadline: u64,
+}
+
+/// Share of every payout sent to `recipient`, in basis points
+#[contracttype]
+#[derive(Clone)]
+pub struct PayoutSplit {
+    pub recipient: Address,
+    pub bps: u32,
+}
+
+/// How a freelancer wants to be paid. Payouts are converted to `preferred_asset`
+/// when the router quotes a rate for it and can deliver within `max_slippage_bps`
+/// of the quote; otherwise they are sent in the job's asset.
+/// An empty `splits` list pays the freelancer's own address.
+#[contracttype]
+#[derive(Clone)]
+pub struct PayoutProfile {
+    pub preferred_asset: Option<Address>,
+    pub max_slippage_bps: u32,
+    pub splits: Vec<PayoutSplit>,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Release an approved milestone and pay the freelancer in `target_asset`.
+    /// Fails unless at least `min_amount_out` is delivered before `deadline`.
+    /// Takes precedence over the asset in the freelancer's payout profile.
+    /// Returns the amount of `target_asset` delivered.
+    pub fn release_payment_with_conversion(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        target_asset: Address,
+        min_amount_out: i128,
+        deadline: u64,
+    ) -> Result<i128, Error> {
+        Self::release_approved(
+            &env,
+            job_id,
+            milestone_id,
+            Some(Conversion {
+                target_asset,
+                min_amount_out,
+                deadline,
+            }),
+        )
+    }
+
+    /// Register or replace the payout profile used for all of a freelancer's jobs
+    pub fn set_payout_profile(env: Env, freelancer: Address, profile: PayoutProfile) -> Result<(), Error> {
+        freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if profile.max_slippage_bps > BPS_DENOMINATOR {
+            return Err(Error::InvalidPayoutProfile);
+        }
+        if !profile.splits.is_empty() {
+            let mut total = 0u32;
+            for split in profile.splits.iter() {
+                if split.bps == 0 {
+                    return Err(Error::InvalidPayoutProfile);
+                }
+                total = total.saturating_add(split.bps);
+            }
+            if total != BPS_DENOMINATOR {
+                return Err(Error::InvalidPayoutProfile);
+            }
+        }
+
+        storage::write_payout_profile(&env, &freelancer, &profile);
+
+        Ok(())
+    }
+
+    /// Remove a freelancer's payout profile: payouts go to their address in the job's asset
+    pub fn clear_payout_profile(env: Env, freelancer: Address) {
+        freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        storage::remove_payout_profile(&env, &freelancer);
+    }
+
+    /// Get a freelancer's payout profile, if any
+    pub fn get_payout_profile(env: Env, freelancer: Address) -> Option<PayoutProfile> {
+        storage::read_payout_profile(&env, &freelancer)
+    }
+}
+
+impl EscrowCore {
+    /// Withdraw `amount` of a job's principal and deliver it to `payee` following their
+    /// payout profile, converted if requested. Returns the yield shared out with it
+    /// and the total amount delivered.
+    pub(crate) fn deliver_payout(
+        env: &Env,
+        job: &Job,
+        milestone_id: u32,
+        amount: i128,
+        payee: &Address,
+        conversion: Option<Conversion>,
+    ) -> Result<(i128, i128), Error> {
+        let profile = storage::read_payout_profile(env, payee);
+        let required = conversion.is_some();
+        let conversion = match conversion {
+            Some(conversion) => Some(conversion),
+            None => profile
+                .as_ref()
+                .and_then(|profile| Self::profile_conversion(env, job, profile, amount)),
+        };
+        let splits = profile.map(|profile| profile.splits).unwrap_or_else(|| Vec::new(env));
+
+        // Split payouts land here first and are then shared out
+        let landing = if splits.is_empty() {
+            payee.clone()
+        } else {
+            env.current_contract_address()
+        };
+
+        let (yield_amount, asset, delivered) = match conversion {
+            Some(conversion) if conversion.target_asset != job.asset_address => {
+                // Principal comes back here and the router takes it from this contract,
+                // so a conversion that fails leaves it here to be paid out unconverted
+                let escrow = env.current_contract_address();
+                let yield_amount = Self::withdraw_principal(env, job, amount, &escrow)?;
+
+                match Self::convert(env, job, &conversion, amount, &landing)? {
+                    Some(delivered) => (yield_amount, conversion.target_asset, delivered),
+                    None if required => return Err(Error::ConversionFailed),
+                    None => {
+                        if landing != escrow {
+                            token::TokenClient::new(env, &job.asset_address).transfer(&escrow, &landing, &amount);
+                        }
+                        (yield_amount, job.asset_address.clone(), amount)
+                    }
+                }
+            }
+            conversion => {
+                // Nothing to convert, but the caller's bounds still apply
+                if let Some(conversion) = &conversion {
+                    Self::check_conversion_bounds(env, conversion, amount)?;
+                }
+                let yield_amount = Self::withdraw_principal(env, job, amount, &landing)?;
+                (yield_amount, job.asset_address.clone(), amount)
+            }
+        };
+
+        if splits.is_empty() {
+            Self::publish_payment(env, job, milestone_id, payee, &asset, delivered);
+        } else {
+            let token_client = token::TokenClient::new(env, &asset);
+            let mut remaining = delivered;
+            for (i, split) in splits.iter().enumerate() {
+                let share = if i as u32 + 1 == splits.len() {
+                    remaining
+                } else {
+                    delivered * split.bps as i128 / BPS_DENOMINATOR as i128
+                };
+                remaining -= share;
+
+                if share > 0 {
+                    token_client.transfer(&env.current_contract_address(), &split.recipient, &share);
+                }
+                Self::publish_payment(env, job, milestone_id, &split.recipient, &asset, share);
+            }
+        }
+
+        Ok((yield_amount, delivered))
+    }
+
+    /// Conversion to the profile's preferred asset, or none when the router has no
+    /// rate for the pair and the payout falls back to the job's asset
+    fn profile_conversion(env: &Env, job: &Job, profile: &PayoutProfile, amount: i128) -> Option<Conversion> {
+        let target_asset = profile.preferred_asset.clone()?;
+        if target_asset == job.asset_address {
+            return None;
+        }
+
+        let router = Self::get_liquidity_router(env.clone()).ok()?;
+        let rate = match LiquidityRouterClient::new(env, &router)
+            .try_get_exchange_rate(&job.asset_address, &target_asset)
+        {
+            Ok(Ok(rate)) if rate > 0 => rate,
+            _ => return None,
+        };
+
+        let quoted = amount.checked_mul(rate)? / RATE_SCALE;
+        Some(Conversion {
+            target_asset,
+            min_amount_out: quoted * (BPS_DENOMINATOR - profile.max_slippage_bps) as i128
+                / BPS_DENOMINATOR as i128,
+            deadline: env.ledger().timestamp(),
+        })
+    }
+
+    /// Convert `amount` of the job's asset held by this contract through the router and
+    /// send it to `recipient`. Returns the amount delivered, or none when the router
+    /// declined and took nothing.
+    fn convert(
+        env: &Env,
+        job: &Job,
+        conversion: &Conversion,
+        amount: i128,
+        recipient: &Address,
+    ) -> Result<Option<i128>, Error> {
+        if env.ledger().timestamp() > conversion.deadline {
+            return Err(Error::DeadlineExpired);
+        }
+        let router = Self::get_liquidity_router(env.clone())?;
+
+        let target_client = token::TokenClient::new(env, &conversion.target_asset);
+        let balance_before = target_client.balance(recipient);
+
+        // The router pulls the input itself. The authorization only lasts until the
+        // next call out of this contract, so it comes right before the router's.
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: job.asset_address.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (env.current_contract_address(), router.clone(), amount).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        match LiquidityRouterClient::new(env, &router).try_convert_and_send(
+            &job.asset_address,
+            &conversion.target_asset,
+            &amount,
+            recipient,
+            &conversion.min_amount_out,
+        ) {
+            Ok(Ok(_)) => {}
+            _ => return Ok(None),
+        }
+
+        // The router's own check is not trusted: measure what actually arrived
+        let delivered = target_client.balance(recipient) - balance_before;
+        Self::check_conversion_bounds(env, conversion, delivered)?;
+        Ok(Some(delivered))
+    }
+
+    fn check_conversion_bounds(env: &Env, conversion: &Conversion, delivered: i128) -> Result<(), Error> {
+        if env.ledger().timestamp() > conversion.deadline {
+            return Err(Error::DeadlineExpired);
+        }
+        if delivered < conversion.min_amount_out {
+            return Err(Error::SlippageExceeded);
+        }
+        Ok(())
+    }
+
+    fn publish_payment(
+        env: &Env,
+        job: &Job,
+        milestone_id: u32,
+        recipient: &Address,
+        asset: &Address,
+        amount: i128,
+    ) {
+        events::PaymentReleased {
+            job_id: job.job_id.clone(),
+            milestone_id,
+            recipient: recipient.clone(),
+            asset: asset.clone(),
+            amount,
+        }
+        .publish(env);
+    }
+}
@@ -0,0 +1,254 @@
+// Open job postings for escrow_core
+// A client can fund a job before choosing a freelancer. The job stays `Open`, with the
+// client standing in as its freelancer, while freelancers submit proposals. Accepting
+// one binds the freelancer, prices the job at the proposal and refunds the difference.
+// A posting holds at most `MAX_PROPOSALS` proposals so that it stays readable.
+// A posting nobody was hired for can be expired after its deadline, refunding the client.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus, Milestone,
+    MilestoneSpec,
+};
+
+/// Most proposals a posting accepts; freelancers can still replace their own
+pub const MAX_PROPOSALS: u32 = 50;
+
+/// A freelancer's offer for an open job: `timeline` is the proposed duration in seconds
+/// and `proposal_hash` commits to the off-chain proposal
+#[contracttype]
+#[derive(Clone)]
+pub struct Proposal {
+    pub freelancer: Address,
+    pub price: i128,
+    pub timeline: u64,
+    pub proposal_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct Posting {
+    pub expires_at: u64,
+    pub proposals: Vec<Proposal>,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Fund a job without a freelancer and open it to proposals until `expires_at`
+    pub fn post_job(
+        env: Env,
+        client: Address,
+        total_amount: i128,
+        asset_address: Address,
+        milestones: Vec<MilestoneSpec>,
+        review_period: u64,
+        expires_at: u64,
+    ) -> Result<BytesN<32>, Error> {
+        if expires_at <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+
+        // The client stands in as freelancer until one is hired
+        let job_id = Self::open_job(
+            &env,
+            client.clone(),
+            client,
+            total_amount,
+            asset_address,
+            milestones,
+            review_period,
+            None,
+        )?;
+
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        job.status = JobStatus::Open;
+        storage::write_job(&env, &job);
+
+        storage::write_posting(
+            &env,
+            &job_id,
+            &Posting {
+                expires_at,
+                proposals: Vec::new(&env),
+            },
+        );
+
+        Ok(job_id)
+    }
+
+    /// Freelancer offers to do an open job for `price`, replacing any earlier proposal
+    pub fn submit_proposal(
+        env: Env,
+        job_id: BytesN<32>,
+        freelancer: Address,
+        price: i128,
+        timeline: u64,
+        proposal_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+        let mut posting = Self::open_posting(&env, &job)?;
+
+        freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if freelancer == job.client || price <= 0 || price > job.total_amount {
+            return Err(Error::InvalidProposal);
+        }
+
+        let proposal = Proposal {
+            freelancer: freelancer.clone(),
+            price,
+            timeline,
+            proposal_hash: proposal_hash.clone(),
+            submitted_at: env.ledger().timestamp(),
+        };
+        match Self::find_proposal(&posting, &freelancer) {
+            Some((index, _)) => posting.proposals.set(index, proposal),
+            None if posting.proposals.len() >= MAX_PROPOSALS => {
+                return Err(Error::TooManyProposals);
+            }
+            None => posting.proposals.push_back(proposal),
+        }
+        storage::write_posting(&env, &job_id, &posting);
+
+        events::ProposalSubmitted {
+            job_id,
+            freelancer,
+            price,
+            timeline,
+            proposal_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Client hires the freelancer behind a proposal. The job is repriced to the
+    /// proposal, using `milestones` when given or scaling the posted milestones
+    /// otherwise, and the difference is refunded with its yield.
+    pub fn accept_proposal(
+        env: Env,
+        job_id: BytesN<32>,
+        freelancer: Address,
+        milestones: Option<Vec<MilestoneSpec>>,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let posting = Self::open_posting(&env, &job)?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let (_, proposal) = Self::find_proposal(&posting, &freelancer).ok_or(Error::ProposalNotFound)?;
+        let price = proposal.price;
+
+        job.milestones = match milestones {
+            Some(specs) => {
+                Self::validate_milestones(price, &specs)?;
+                Self::new_milestones(&env, &specs)
+            }
+            None => Self::scale_milestones(&job, price)?,
+        };
+
+        let refund_amount = job.total_amount - price;
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        job.freelancer = freelancer.clone();
+        job.total_amount = price;
+        job.status = JobStatus::Active;
+        storage::write_job(&env, &job);
+        storage::index_freelancer_job(&env, &freelancer, &job_id);
+
+        events::ProposalAccepted {
+            job_id,
+            freelancer,
+            price,
+            refund_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Close a posting nobody was hired for once it has expired and refund the client.
+    /// Anyone can call this.
+    pub fn expire_posting(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let posting = storage::read_posting(&env, &job_id).ok_or(Error::JobNotOpen)?;
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Open {
+            return Err(Error::JobNotOpen);
+        }
+        if env.ledger().timestamp() < posting.expires_at {
+            return Err(Error::PostingNotExpired);
+        }
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        let refund_amount = ledger.balance();
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        job.status = JobStatus::Cancelled;
+        storage::write_job(&env, &job);
+
+        events::PostingExpired {
+            job_id,
+            refund_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the posting of a job opened without a freelancer, with its proposals
+    pub fn get_posting(env: Env, job_id: BytesN<32>) -> Result<Posting, Error> {
+        storage::read_posting(&env, &job_id).ok_or(Error::JobNotOpen)
+    }
+}
+
+impl EscrowCore {
+    /// The posting of a job still open to proposals
+    fn open_posting(env: &Env, job: &Job) -> Result<Posting, Error> {
+        let posting = storage::read_posting(env, &job.job_id).ok_or(Error::JobNotOpen)?;
+        if job.status != JobStatus::Open {
+            return Err(Error::JobNotOpen);
+        }
+        if env.ledger().timestamp() >= posting.expires_at {
+            return Err(Error::PostingExpired);
+        }
+        Ok(posting)
+    }
+
+    fn find_proposal(posting: &Posting, freelancer: &Address) -> Option<(u32, Proposal)> {
+        posting
+            .proposals
+            .iter()
+            .enumerate()
+            .find(|(_, proposal)| proposal.freelancer == *freelancer)
+            .map(|(index, proposal)| (index as u32, proposal))
+    }
+
+    /// Scale a job's milestones to add up to `price`, the last one taking the remainder
+    fn scale_milestones(job: &Job, price: i128) -> Result<Vec<Milestone>, Error> {
+        let mut milestones = job.milestones.clone();
+        let count = milestones.len();
+        let mut allocated = 0i128;
+        for index in 0..count {
+            let mut milestone = milestones.get(index).unwrap();
+            milestone.amount = if index + 1 == count {
+                price - allocated
+            } else {
+                milestone.amount * price / job.total_amount
+            };
+            if milestone.amount <= 0 {
+                return Err(Error::InvalidMilestoneAmount);
+            }
+            allocated += milestone.amount;
+            milestones.set(index, milestone);
+        }
+        Ok(milestones)
+    }
+}
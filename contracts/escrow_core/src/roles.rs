@@ -0,0 +1,173 @@
+// Delegated job roles for escrow_core
+// A client can let other accounts act on a job for them. Reviewers approve or reject
+// submitted proof but never move funds. Payers release payments, cancel the job and
+// agree change orders on the client's behalf; funding a change order comes from the
+// payer's own account, while refunds still go to the client.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job};
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub enum JobRole {
+    Reviewer,
+    Payer,
+}
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub struct RoleGrant {
+    pub account: Address,
+    pub role: JobRole,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Client grants `role` on a job to `account`
+    pub fn grant_role(
+        env: Env,
+        job_id: BytesN<32>,
+        account: Address,
+        role: JobRole,
+    ) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        // The freelancer must not review or pay their own work
+        if account == job.client || account == job.freelancer {
+            return Err(Error::InvalidRoleAccount);
+        }
+
+        let grant = RoleGrant {
+            account: account.clone(),
+            role: role.clone(),
+        };
+        let mut roles = storage::read_job_roles(&env, &job_id);
+        if roles.contains(&grant) {
+            return Err(Error::RoleAlreadyGranted);
+        }
+        roles.push_back(grant);
+        storage::write_job_roles(&env, &job_id, &roles);
+
+        events::RoleGranted {
+            job_id,
+            account,
+            role,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Client takes `role` on a job away from `account`
+    pub fn revoke_role(
+        env: Env,
+        job_id: BytesN<32>,
+        account: Address,
+        role: JobRole,
+    ) -> Result<(), Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut roles = storage::read_job_roles(&env, &job_id);
+        let index = roles
+            .first_index_of(RoleGrant {
+                account: account.clone(),
+                role: role.clone(),
+            })
+            .ok_or(Error::RoleNotGranted)?;
+        roles.remove(index);
+        storage::write_job_roles(&env, &job_id, &roles);
+
+        events::RoleRevoked {
+            job_id,
+            account,
+            role,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the roles granted on a job
+    pub fn get_job_roles(env: Env, job_id: BytesN<32>) -> Vec<RoleGrant> {
+        storage::read_job_roles(&env, &job_id)
+    }
+
+    /// Client or reviewer approves (`approved`) or rejects a milestone's submitted proof
+    pub fn review_milestone(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        reviewer: Address,
+        approved: bool,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id)?;
+
+        Self::require_client_side(&env, &job, &reviewer, JobRole::Reviewer)?;
+        storage::extend_instance_ttl(&env);
+
+        if approved {
+            Self::require_no_organization(&env, &job)?;
+            Self::approve(&env, &mut job, milestone_id)
+        } else {
+            Self::reject(&env, &mut job, milestone_id)
+        }
+    }
+
+    /// Client or payer releases payment for an approved milestone
+    pub fn release_payment_as(
+        env: Env,
+        job_id: BytesN<32>,
+        milestone_id: u32,
+        payer: Address,
+    ) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id)?;
+
+        Self::require_client_side(&env, &job, &payer, JobRole::Payer)?;
+        storage::extend_instance_ttl(&env);
+        Self::require_no_organization(&env, &job)?;
+
+        Self::release(&env, &mut job, milestone_id, None)?;
+        Ok(())
+    }
+
+    /// Client or payer cancels the job and refunds the client
+    pub fn cancel_job_as(env: Env, job_id: BytesN<32>, payer: Address) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id)?;
+
+        Self::require_client_side(&env, &job, &payer, JobRole::Payer)?;
+        storage::extend_instance_ttl(&env);
+
+        Self::cancel(&env, &mut job)
+    }
+}
+
+impl EscrowCore {
+    /// Whether `account` is the job's client or holds `role` on it
+    pub(crate) fn acts_for_client(env: &Env, job: &Job, account: &Address, role: JobRole) -> bool {
+        *account == job.client
+            || storage::read_job_roles(env, &job.job_id).contains(RoleGrant {
+                account: account.clone(),
+                role,
+            })
+    }
+
+    /// Require `account`'s authorization as the job's client or a holder of `role`
+    pub(crate) fn require_client_side(
+        env: &Env,
+        job: &Job,
+        account: &Address,
+        role: JobRole,
+    ) -> Result<(), Error> {
+        account.require_auth();
+        if !Self::acts_for_client(env, job, account, role) {
+            return Err(Error::Unauthorized);
+        }
+        Ok(())
+    }
+}
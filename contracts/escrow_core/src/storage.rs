@@ -1,2 +1,704 @@
 // Storage module for escrow_core
-// This file is reserved for future storage-related utilities
+// Job records live in persistent storage under typed keys so that each call
+// only loads the entries it touches. Contract configuration stays in instance storage.
+use soroban_sdk::{contracttype, Address, BytesN, Env, Vec};
+
+use crate::{
+    ChangeOrder, Dispute, HourlyTerms, Job, JobLedger, OrgAction, Organization, Payee, PayoutProfile, Posting, ProofAttachment, RoleGrant, Stream, TimeLog,
+    YieldIndex, YieldPolicy, YieldShares, YieldStrategy, BPS_DENOMINATOR,
+};
+
+const DAY_IN_LEDGERS: u32 = 17280;
+
+pub(crate) const INSTANCE_BUMP_AMOUNT: u32 = 7 * DAY_IN_LEDGERS;
+pub(crate) const INSTANCE_LIFETIME_THRESHOLD: u32 = INSTANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+pub(crate) const JOB_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+pub(crate) const JOB_LIFETIME_THRESHOLD: u32 = JOB_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Job(BytesN<32>),
+    Ledger(BytesN<32>),
+    Proofs(BytesN<32>, u32),
+    JobArbiter(BytesN<32>),
+    Dispute(BytesN<32>, u32),
+    AssetStrategy(Address),
+    StrategyPrincipal(Address, Address),
+    ClientFee(Address),
+    CollectedFees(Address),
+    PayoutProfile(Address),
+    MilestonePayees(BytesN<32>, u32),
+    Stream(BytesN<32>),
+    HourlyTerms(BytesN<32>),
+    TimeLog(BytesN<32>, u32),
+    ChangeOrders(BytesN<32>),
+    Posting(BytesN<32>),
+    Organization(Address),
+    Approvals(BytesN<32>, u32, OrgAction),
+    JobRoles(BytesN<32>),
+    YieldPolicy(BytesN<32>),
+    YieldShares(BytesN<32>),
+    UnclaimedYield(BytesN<32>),
+    StrategyUnclaimedYield(Address, Address),
+    ClientJobs(Address),
+    ClientJob(Address, u32),
+    FreelancerJobs(Address),
+    FreelancerJob(Address, u32),
+    MilestoneCounter(BytesN<32>),
+    YieldIndex(Address, Address),
+    YieldSnapshot(BytesN<32>),
+}
+
+/// Extend the TTL of the contract instance (configuration and counters)
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+}
+
+/// Extend the TTL of every persistent entry belonging to a job
+pub fn extend_job_ttl(env: &Env, job: &Job) {
+    let storage = env.storage().persistent();
+    let mut keys = Vec::new(env);
+    keys.push_back(DataKey::Job(job.job_id.clone()));
+    keys.push_back(DataKey::Ledger(job.job_id.clone()));
+    keys.push_back(DataKey::JobArbiter(job.job_id.clone()));
+    keys.push_back(DataKey::Stream(job.job_id.clone()));
+    keys.push_back(DataKey::HourlyTerms(job.job_id.clone()));
+    keys.push_back(DataKey::ChangeOrders(job.job_id.clone()));
+    keys.push_back(DataKey::Posting(job.job_id.clone()));
+    keys.push_back(DataKey::JobRoles(job.job_id.clone()));
+    keys.push_back(DataKey::YieldPolicy(job.job_id.clone()));
+    keys.push_back(DataKey::YieldShares(job.job_id.clone()));
+    keys.push_back(DataKey::UnclaimedYield(job.job_id.clone()));
+    keys.push_back(DataKey::MilestoneCounter(job.job_id.clone()));
+    keys.push_back(DataKey::YieldSnapshot(job.job_id.clone()));
+    for milestone in job.milestones.iter() {
+        keys.push_back(DataKey::Proofs(job.job_id.clone(), milestone.milestone_id));
+        keys.push_back(DataKey::Dispute(job.job_id.clone(), milestone.milestone_id));
+        keys.push_back(DataKey::MilestonePayees(job.job_id.clone(), milestone.milestone_id));
+        keys.push_back(DataKey::TimeLog(job.job_id.clone(), milestone.milestone_id));
+        keys.push_back(DataKey::Approvals(job.job_id.clone(), milestone.milestone_id, OrgAction::Approve));
+        keys.push_back(DataKey::Approvals(job.job_id.clone(), milestone.milestone_id, OrgAction::Release));
+    }
+
+    for key in keys.iter() {
+        if storage.has(&key) {
+            storage.extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+        }
+    }
+}
+
+/// Read a job, extending its TTL when it exists
+pub fn read_job(env: &Env, job_id: &BytesN<32>) -> Option<Job> {
+    let key = DataKey::Job(job_id.clone());
+    let job = env.storage().persistent().get::<_, Job>(&key);
+    if job.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    job
+}
+
+/// Write a job and extend its TTL
+pub fn write_job(env: &Env, job: &Job) {
+    let key = DataKey::Job(job.job_id.clone());
+    env.storage().persistent().set(&key, job);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the escrow ledger of a job (empty if nothing was funded yet)
+pub fn read_ledger(env: &Env, job_id: &BytesN<32>) -> JobLedger {
+    let key = DataKey::Ledger(job_id.clone());
+    match env.storage().persistent().get::<_, JobLedger>(&key) {
+        Some(ledger) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            ledger
+        }
+        None => JobLedger::default(),
+    }
+}
+
+/// Write the escrow ledger of a job and extend its TTL
+pub fn write_ledger(env: &Env, job_id: &BytesN<32>, ledger: &JobLedger) {
+    let key = DataKey::Ledger(job_id.clone());
+    env.storage().persistent().set(&key, ledger);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the proof attachments submitted for a milestone
+pub fn read_proofs(env: &Env, job_id: &BytesN<32>, milestone_id: u32) -> Vec<ProofAttachment> {
+    let key = DataKey::Proofs(job_id.clone(), milestone_id);
+    env.storage()
+        .persistent()
+        .get(&key)
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Write the proof attachments of a milestone and extend their TTL
+pub fn write_proofs(env: &Env, job_id: &BytesN<32>, milestone_id: u32, proofs: &Vec<ProofAttachment>) {
+    let key = DataKey::Proofs(job_id.clone(), milestone_id);
+    env.storage().persistent().set(&key, proofs);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove the proof attachments of a milestone
+pub fn remove_proofs(env: &Env, job_id: &BytesN<32>, milestone_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Proofs(job_id.clone(), milestone_id));
+}
+
+/// Read the arbiter both parties agreed on for a job, if any
+pub fn read_job_arbiter(env: &Env, job_id: &BytesN<32>) -> Option<Address> {
+    env.storage().persistent().get(&DataKey::JobArbiter(job_id.clone()))
+}
+
+/// Write the arbiter of a job and extend its TTL
+pub fn write_job_arbiter(env: &Env, job_id: &BytesN<32>, arbiter: &Address) {
+    let key = DataKey::JobArbiter(job_id.clone());
+    env.storage().persistent().set(&key, arbiter);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the dispute raised on a milestone, if any
+pub fn read_dispute(env: &Env, job_id: &BytesN<32>, milestone_id: u32) -> Option<Dispute> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Dispute(job_id.clone(), milestone_id))
+}
+
+/// Write the dispute of a milestone and extend its TTL
+pub fn write_dispute(env: &Env, dispute: &Dispute) {
+    let key = DataKey::Dispute(dispute.job_id.clone(), dispute.milestone_id);
+    env.storage().persistent().set(&key, dispute);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the default yield strategy configured for an asset, if any
+pub fn read_asset_strategy(env: &Env, asset: &Address) -> Option<YieldStrategy> {
+    let key = DataKey::AssetStrategy(asset.clone());
+    let strategy = env.storage().persistent().get::<_, YieldStrategy>(&key);
+    if strategy.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    strategy
+}
+
+/// Write the default yield strategy of an asset and extend its TTL
+pub fn write_asset_strategy(env: &Env, asset: &Address, strategy: &YieldStrategy) {
+    let key = DataKey::AssetStrategy(asset.clone());
+    env.storage().persistent().set(&key, strategy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the principal this contract holds in a strategy for an asset
+pub fn read_strategy_principal(env: &Env, strategy: &Address, asset: &Address) -> i128 {
+    let key = DataKey::StrategyPrincipal(strategy.clone(), asset.clone());
+    match env.storage().persistent().get::<_, i128>(&key) {
+        Some(principal) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            principal
+        }
+        None => 0,
+    }
+}
+
+/// Write the principal held in a strategy for an asset and extend its TTL
+pub fn write_strategy_principal(env: &Env, strategy: &Address, asset: &Address, principal: i128) {
+    let key = DataKey::StrategyPrincipal(strategy.clone(), asset.clone());
+    env.storage().persistent().set(&key, &principal);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read a client's fee override, if any
+pub fn read_client_fee(env: &Env, client: &Address) -> Option<u32> {
+    let key = DataKey::ClientFee(client.clone());
+    let fee_bps = env.storage().persistent().get::<_, u32>(&key);
+    if fee_bps.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    fee_bps
+}
+
+/// Write a client's fee override and extend its TTL
+pub fn write_client_fee(env: &Env, client: &Address, fee_bps: u32) {
+    let key = DataKey::ClientFee(client.clone());
+    env.storage().persistent().set(&key, &fee_bps);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove a client's fee override
+pub fn remove_client_fee(env: &Env, client: &Address) {
+    env.storage().persistent().remove(&DataKey::ClientFee(client.clone()));
+}
+
+/// Read the fees collected so far in an asset
+pub fn read_collected_fees(env: &Env, asset: &Address) -> i128 {
+    let key = DataKey::CollectedFees(asset.clone());
+    match env.storage().persistent().get::<_, i128>(&key) {
+        Some(collected) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            collected
+        }
+        None => 0,
+    }
+}
+
+/// Write the fees collected in an asset and extend their TTL
+pub fn write_collected_fees(env: &Env, asset: &Address, collected: i128) {
+    let key = DataKey::CollectedFees(asset.clone());
+    env.storage().persistent().set(&key, &collected);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read a freelancer's payout profile, if any
+pub fn read_payout_profile(env: &Env, freelancer: &Address) -> Option<PayoutProfile> {
+    let key = DataKey::PayoutProfile(freelancer.clone());
+    let profile = env.storage().persistent().get::<_, PayoutProfile>(&key);
+    if profile.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    profile
+}
+
+/// Write a freelancer's payout profile and extend its TTL
+pub fn write_payout_profile(env: &Env, freelancer: &Address, profile: &PayoutProfile) {
+    let key = DataKey::PayoutProfile(freelancer.clone());
+    env.storage().persistent().set(&key, profile);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove a freelancer's payout profile
+pub fn remove_payout_profile(env: &Env, freelancer: &Address) {
+    env.storage().persistent().remove(&DataKey::PayoutProfile(freelancer.clone()));
+}
+
+/// Read the payees of a milestone, if it does not simply pay the lead freelancer
+pub fn read_milestone_payees(env: &Env, job_id: &BytesN<32>, milestone_id: u32) -> Option<Vec<Payee>> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::MilestonePayees(job_id.clone(), milestone_id))
+}
+
+/// Write the payees of a milestone and extend their TTL
+pub fn write_milestone_payees(env: &Env, job_id: &BytesN<32>, milestone_id: u32, payees: &Vec<Payee>) {
+    let key = DataKey::MilestonePayees(job_id.clone(), milestone_id);
+    env.storage().persistent().set(&key, payees);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove the payees of a milestone: it pays the lead freelancer again
+pub fn remove_milestone_payees(env: &Env, job_id: &BytesN<32>, milestone_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::MilestonePayees(job_id.clone(), milestone_id));
+}
+
+/// Read the vesting terms of a streaming job, if it is one
+pub fn read_stream(env: &Env, job_id: &BytesN<32>) -> Option<Stream> {
+    env.storage().persistent().get(&DataKey::Stream(job_id.clone()))
+}
+
+/// Write the vesting terms of a streaming job and extend their TTL
+pub fn write_stream(env: &Env, job_id: &BytesN<32>, stream: &Stream) {
+    let key = DataKey::Stream(job_id.clone());
+    env.storage().persistent().set(&key, stream);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the last milestone id handed out on a job, if one was ever recorded
+pub fn read_milestone_counter(env: &Env, job_id: &BytesN<32>) -> Option<u32> {
+    env.storage().persistent().get(&DataKey::MilestoneCounter(job_id.clone()))
+}
+
+/// Write the last milestone id handed out on a job and extend its TTL
+pub fn write_milestone_counter(env: &Env, job_id: &BytesN<32>, milestone_id: u32) {
+    let key = DataKey::MilestoneCounter(job_id.clone());
+    env.storage().persistent().set(&key, &milestone_id);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the terms of an hourly job, if it is one
+pub fn read_hourly_terms(env: &Env, job_id: &BytesN<32>) -> Option<HourlyTerms> {
+    env.storage().persistent().get(&DataKey::HourlyTerms(job_id.clone()))
+}
+
+/// Write the terms of an hourly job and extend their TTL
+pub fn write_hourly_terms(env: &Env, job_id: &BytesN<32>, terms: &HourlyTerms) {
+    let key = DataKey::HourlyTerms(job_id.clone());
+    env.storage().persistent().set(&key, terms);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the time log behind an hourly job's milestone, if any
+pub fn read_time_log(env: &Env, job_id: &BytesN<32>, milestone_id: u32) -> Option<TimeLog> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::TimeLog(job_id.clone(), milestone_id))
+}
+
+/// Write the time log behind a milestone and extend its TTL
+pub fn write_time_log(env: &Env, job_id: &BytesN<32>, milestone_id: u32, log: &TimeLog) {
+    let key = DataKey::TimeLog(job_id.clone(), milestone_id);
+    env.storage().persistent().set(&key, log);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read every change order proposed on a job, oldest first
+pub fn read_change_orders(env: &Env, job_id: &BytesN<32>) -> Vec<ChangeOrder> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::ChangeOrders(job_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Write the change orders of a job and extend their TTL
+pub fn write_change_orders(env: &Env, job_id: &BytesN<32>, orders: &Vec<ChangeOrder>) {
+    let key = DataKey::ChangeOrders(job_id.clone());
+    env.storage().persistent().set(&key, orders);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove the time log behind a milestone
+pub fn remove_time_log(env: &Env, job_id: &BytesN<32>, milestone_id: u32) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::TimeLog(job_id.clone(), milestone_id));
+}
+
+/// Read the posting of a job opened without a freelancer, if any
+pub fn read_posting(env: &Env, job_id: &BytesN<32>) -> Option<Posting> {
+    env.storage().persistent().get(&DataKey::Posting(job_id.clone()))
+}
+
+/// Write the posting of a job and extend its TTL
+pub fn write_posting(env: &Env, job_id: &BytesN<32>, posting: &Posting) {
+    let key = DataKey::Posting(job_id.clone());
+    env.storage().persistent().set(&key, posting);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the organization that signs for a client account, if any
+pub fn read_organization(env: &Env, account: &Address) -> Option<Organization> {
+    let key = DataKey::Organization(account.clone());
+    let organization = env.storage().persistent().get::<_, Organization>(&key);
+    if organization.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    organization
+}
+
+/// Write the organization of a client account and extend its TTL
+pub fn write_organization(env: &Env, account: &Address, organization: &Organization) {
+    let key = DataKey::Organization(account.clone());
+    env.storage().persistent().set(&key, organization);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove the organization of a client account
+pub fn remove_organization(env: &Env, account: &Address) {
+    env.storage().persistent().remove(&DataKey::Organization(account.clone()));
+}
+
+/// Read the members who signed an action on a milestone so far
+pub fn read_approvals(env: &Env, job_id: &BytesN<32>, milestone_id: u32, action: &OrgAction) -> Vec<Address> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Approvals(job_id.clone(), milestone_id, action.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Write the members who signed an action on a milestone and extend their TTL
+pub fn write_approvals(
+    env: &Env,
+    job_id: &BytesN<32>,
+    milestone_id: u32,
+    action: &OrgAction,
+    approvals: &Vec<Address>,
+) {
+    let key = DataKey::Approvals(job_id.clone(), milestone_id, action.clone());
+    env.storage().persistent().set(&key, approvals);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Remove the signatures collected for an action on a milestone
+pub fn remove_approvals(env: &Env, job_id: &BytesN<32>, milestone_id: u32, action: &OrgAction) {
+    env.storage()
+        .persistent()
+        .remove(&DataKey::Approvals(job_id.clone(), milestone_id, action.clone()));
+}
+
+/// Read the roles granted on a job
+pub fn read_job_roles(env: &Env, job_id: &BytesN<32>) -> Vec<RoleGrant> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::JobRoles(job_id.clone()))
+        .unwrap_or_else(|| Vec::new(env))
+}
+
+/// Write the roles granted on a job and extend their TTL
+pub fn write_job_roles(env: &Env, job_id: &BytesN<32>, roles: &Vec<RoleGrant>) {
+    let key = DataKey::JobRoles(job_id.clone());
+    env.storage().persistent().set(&key, roles);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read how a job's yield is split; all of it goes to the client unless a policy was set
+pub fn read_yield_policy(env: &Env, job_id: &BytesN<32>) -> YieldPolicy {
+    env.storage()
+        .persistent()
+        .get(&DataKey::YieldPolicy(job_id.clone()))
+        .unwrap_or(YieldPolicy {
+            client_bps: BPS_DENOMINATOR,
+            freelancer_bps: 0,
+            platform_bps: 0,
+        })
+}
+
+/// Write the yield policy of a job and extend its TTL
+pub fn write_yield_policy(env: &Env, job_id: &BytesN<32>, policy: &YieldPolicy) {
+    let key = DataKey::YieldPolicy(job_id.clone());
+    env.storage().persistent().set(&key, policy);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the yield a job has paid to each party so far
+pub fn read_yield_shares(env: &Env, job_id: &BytesN<32>) -> YieldShares {
+    env.storage()
+        .persistent()
+        .get(&DataKey::YieldShares(job_id.clone()))
+        .unwrap_or_default()
+}
+
+/// Write the yield a job has paid to each party and extend its TTL
+pub fn write_yield_shares(env: &Env, job_id: &BytesN<32>, shares: &YieldShares) {
+    let key = DataKey::YieldShares(job_id.clone());
+    env.storage().persistent().set(&key, shares);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the yield attributed to a job that its strategy could not pay out yet
+pub fn read_unclaimed_yield(env: &Env, job_id: &BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::UnclaimedYield(job_id.clone()))
+        .unwrap_or(0)
+}
+
+/// Write the yield still owed to a job and extend its TTL
+pub fn write_unclaimed_yield(env: &Env, job_id: &BytesN<32>, amount: i128) {
+    let key = DataKey::UnclaimedYield(job_id.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the yield index of a strategy position for an asset
+pub fn read_yield_index(env: &Env, strategy: &Address, asset: &Address) -> YieldIndex {
+    let key = DataKey::YieldIndex(strategy.clone(), asset.clone());
+    match env.storage().persistent().get::<_, YieldIndex>(&key) {
+        Some(index) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            index
+        }
+        None => YieldIndex::default(),
+    }
+}
+
+/// Write the yield index of a strategy position and extend its TTL
+pub fn write_yield_index(env: &Env, strategy: &Address, asset: &Address, index: &YieldIndex) {
+    let key = DataKey::YieldIndex(strategy.clone(), asset.clone());
+    env.storage().persistent().set(&key, index);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the yield index a job's principal earns from. Jobs funded before the index
+/// existed start from zero.
+pub fn read_yield_snapshot(env: &Env, job_id: &BytesN<32>) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::YieldSnapshot(job_id.clone()))
+        .unwrap_or(0)
+}
+
+/// Write the yield index a job's principal earns from and extend its TTL
+pub fn write_yield_snapshot(env: &Env, job_id: &BytesN<32>, snapshot: i128) {
+    let key = DataKey::YieldSnapshot(job_id.clone());
+    env.storage().persistent().set(&key, &snapshot);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Read the yield held in a strategy position for an asset that is already owed to
+/// particular jobs
+pub fn read_strategy_unclaimed_yield(env: &Env, strategy: &Address, asset: &Address) -> i128 {
+    let key = DataKey::StrategyUnclaimedYield(strategy.clone(), asset.clone());
+    match env.storage().persistent().get::<_, i128>(&key) {
+        Some(amount) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            amount
+        }
+        None => 0,
+    }
+}
+
+/// Write the yield in a strategy position that is owed to particular jobs
+pub fn write_strategy_unclaimed_yield(env: &Env, strategy: &Address, asset: &Address, amount: i128) {
+    let key = DataKey::StrategyUnclaimedYield(strategy.clone(), asset.clone());
+    env.storage().persistent().set(&key, &amount);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
+
+/// Add a job to the jobs listed for a client
+pub fn index_client_job(env: &Env, client: &Address, job_id: &BytesN<32>) {
+    let count = read_client_job_count(env, client);
+    write_party_job(env, DataKey::ClientJobs(client.clone()), DataKey::ClientJob(client.clone(), count), count, job_id);
+}
+
+/// Number of jobs listed for a client
+pub fn read_client_job_count(env: &Env, client: &Address) -> u32 {
+    read_party_job_count(env, &DataKey::ClientJobs(client.clone()))
+}
+
+/// Ids of up to `limit` of a client's jobs, starting at position `start`
+pub fn read_client_job_ids(env: &Env, client: &Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+    let end = start.saturating_add(limit).min(read_client_job_count(env, client));
+    let mut job_ids = Vec::new(env);
+    for index in start..end {
+        if let Some(job_id) = read_party_job(env, &DataKey::ClientJob(client.clone(), index)) {
+            job_ids.push_back(job_id);
+        }
+    }
+    job_ids
+}
+
+/// Add a job to the jobs listed for a freelancer
+pub fn index_freelancer_job(env: &Env, freelancer: &Address, job_id: &BytesN<32>) {
+    let count = read_freelancer_job_count(env, freelancer);
+    write_party_job(
+        env,
+        DataKey::FreelancerJobs(freelancer.clone()),
+        DataKey::FreelancerJob(freelancer.clone(), count),
+        count,
+        job_id,
+    );
+}
+
+/// Number of jobs listed for a freelancer
+pub fn read_freelancer_job_count(env: &Env, freelancer: &Address) -> u32 {
+    read_party_job_count(env, &DataKey::FreelancerJobs(freelancer.clone()))
+}
+
+/// Ids of up to `limit` of a freelancer's jobs, starting at position `start`
+pub fn read_freelancer_job_ids(env: &Env, freelancer: &Address, start: u32, limit: u32) -> Vec<BytesN<32>> {
+    let end = start.saturating_add(limit).min(read_freelancer_job_count(env, freelancer));
+    let mut job_ids = Vec::new(env);
+    for index in start..end {
+        if let Some(job_id) = read_party_job(env, &DataKey::FreelancerJob(freelancer.clone(), index)) {
+            job_ids.push_back(job_id);
+        }
+    }
+    job_ids
+}
+
+fn read_party_job_count(env: &Env, count_key: &DataKey) -> u32 {
+    match env.storage().persistent().get::<_, u32>(count_key) {
+        Some(count) => {
+            env.storage()
+                .persistent()
+                .extend_ttl(count_key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+            count
+        }
+        None => 0,
+    }
+}
+
+fn read_party_job(env: &Env, entry_key: &DataKey) -> Option<BytesN<32>> {
+    let job_id = env.storage().persistent().get::<_, BytesN<32>>(entry_key);
+    if job_id.is_some() {
+        env.storage()
+            .persistent()
+            .extend_ttl(entry_key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    }
+    job_id
+}
+
+fn write_party_job(env: &Env, count_key: DataKey, entry_key: DataKey, count: u32, job_id: &BytesN<32>) {
+    let storage = env.storage().persistent();
+    storage.set(&entry_key, job_id);
+    storage.extend_ttl(&entry_key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+    storage.set(&count_key, &(count + 1));
+    storage.extend_ttl(&count_key, JOB_LIFETIME_THRESHOLD, JOB_BUMP_AMOUNT);
+}
@@ -0,0 +1,263 @@
+// Yield strategies for escrow_core
+// Each job's escrow is held by a yield strategy chosen when the job is created: either
+// a contract implementing `YieldStrategyInterface` or no strategy at all, in which case
+// the funds stay in this contract. Jobs without an explicit choice use the strategy set
+// for their asset, or the YieldHarvester given at initialization.
+//
+// Jobs sharing a strategy position earn its yield by principal and time held. Before
+// any principal moves, the yield accrued since the last move is spread over the
+// principal held meanwhile, raising the position's cumulative yield per unit of
+// principal. A job's principal earns the index's growth since its snapshot.
+use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
+use soroban_sdk::{contractimpl, contracttype, token, vec, Address, BytesN, Env, IntoVal, Symbol, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job,
+    YieldStrategyClient,
+};
+
+#[contracttype]
+#[derive(Clone, PartialEq, Eq)]
+pub enum YieldStrategy {
+    /// Funds are held by the escrow contract and earn nothing
+    NoYield,
+    /// Funds are deposited into a contract implementing `YieldStrategyInterface`
+    Contract(Address),
+}
+
+/// Fixed-point scale of `YieldIndex::index`
+pub const YIELD_INDEX_SCALE: i128 = 1_000_000_000_000;
+
+/// Yield of a strategy position: `index` is the cumulative yield per unit of principal,
+/// scaled by `YIELD_INDEX_SCALE`, and `accounted` the yield it covers that jobs have
+/// not yet withdrawn
+#[contracttype]
+#[derive(Clone, Default)]
+pub struct YieldIndex {
+    pub index: i128,
+    pub accounted: i128,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Admin: allow a strategy contract to hold job escrow
+    pub fn approve_strategy(env: Env, strategy: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut strategies = Self::get_strategies(env.clone());
+        if strategies.contains(&strategy) {
+            return Err(Error::StrategyAlreadyApproved);
+        }
+        strategies.push_back(strategy.clone());
+        env.storage().instance().set(&Symbol::new(&env, "strategies"), &strategies);
+
+        events::StrategyApproved { strategy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Admin: stop new jobs from using a strategy contract.
+    /// Jobs already funded through it keep withdrawing from it.
+    pub fn revoke_strategy(env: Env, strategy: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        let mut strategies = Self::get_strategies(env.clone());
+        let index = strategies
+            .first_index_of(&strategy)
+            .ok_or(Error::StrategyNotApproved)?;
+        strategies.remove(index);
+        env.storage().instance().set(&Symbol::new(&env, "strategies"), &strategies);
+
+        events::StrategyRevoked { strategy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the approved strategy contracts (the initial YieldHarvester is always allowed)
+    pub fn get_strategies(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&Symbol::new(&env, "strategies"))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Admin: set the default strategy for jobs funded in `asset`
+    pub fn set_asset_strategy(env: Env, asset: Address, strategy: YieldStrategy) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        Self::require_approved_strategy(&env, &strategy)?;
+        storage::write_asset_strategy(&env, &asset, &strategy);
+
+        events::AssetStrategySet { asset, strategy }.publish(&env);
+
+        Ok(())
+    }
+
+    /// Get the strategy new jobs in `asset` use when none is chosen
+    pub fn get_asset_strategy(env: Env, asset: Address) -> Result<YieldStrategy, Error> {
+        match storage::read_asset_strategy(&env, &asset) {
+            Some(strategy) => Ok(strategy),
+            None => Ok(YieldStrategy::Contract(Self::get_yield_harvester(&env)?)),
+        }
+    }
+
+    /// Get the yield index of this contract's position in `strategy` for `asset`
+    pub fn get_yield_index(env: Env, strategy: Address, asset: Address) -> YieldIndex {
+        storage::read_yield_index(&env, &strategy, &asset)
+    }
+}
+
+impl EscrowCore {
+    /// Pick the strategy for a new job: the requested one if approved, else the asset default
+    pub(crate) fn resolve_strategy(
+        env: &Env,
+        asset: &Address,
+        requested: Option<YieldStrategy>,
+    ) -> Result<YieldStrategy, Error> {
+        match requested {
+            Some(strategy) => {
+                Self::require_approved_strategy(env, &strategy)?;
+                Ok(strategy)
+            }
+            None => Self::get_asset_strategy(env.clone(), asset.clone()),
+        }
+    }
+
+    fn require_approved_strategy(env: &Env, strategy: &YieldStrategy) -> Result<(), Error> {
+        match strategy {
+            YieldStrategy::NoYield => Ok(()),
+            YieldStrategy::Contract(address) => {
+                if *address == Self::get_yield_harvester(env)?
+                    || Self::get_strategies(env.clone()).contains(address)
+                {
+                    Ok(())
+                } else {
+                    Err(Error::StrategyNotApproved)
+                }
+            }
+        }
+    }
+
+    /// Move `amount` of the job's asset from `from` into the job's strategy
+    pub(crate) fn deposit_principal(env: &Env, job: &Job, from: &Address, amount: i128) {
+        let principal = storage::read_ledger(env, &job.job_id).balance();
+        let token_client = token::TokenClient::new(env, &job.asset_address);
+        match &job.strategy {
+            YieldStrategy::NoYield => {
+                token_client.transfer(from, env.current_contract_address(), &amount);
+            }
+            YieldStrategy::Contract(strategy) => {
+                if *from != env.current_contract_address() {
+                    token_client.transfer(from, env.current_contract_address(), &amount);
+                }
+                Self::deposit_to_strategy(env, strategy, &job.asset_address, &job.job_id, principal, amount);
+            }
+        }
+    }
+
+    /// Deposit `amount` of `asset` held by this contract into `strategy` for a job that
+    /// already holds `principal` there. The strategy pulls the tokens itself, so the
+    /// transfer is authorized up front.
+    pub(crate) fn deposit_to_strategy(
+        env: &Env,
+        strategy: &Address,
+        asset: &Address,
+        job_id: &BytesN<32>,
+        principal: i128,
+        amount: i128,
+    ) {
+        // The new principal earns from the current index on; what the job's existing
+        // principal has earned is kept by averaging the snapshots, rounding up
+        let index = Self::sync_yield_index(env, strategy, asset).index;
+        let snapshot = storage::read_yield_snapshot(env, job_id);
+        let total = principal + amount;
+        let weighted = snapshot * principal + index * amount;
+        storage::write_yield_snapshot(env, job_id, (weighted + total - 1) / total);
+
+        let owner = env.current_contract_address();
+        env.authorize_as_current_contract(vec![
+            env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: asset.clone(),
+                    fn_name: Symbol::new(env, "transfer"),
+                    args: (owner.clone(), strategy.clone(), amount).into_val(env),
+                },
+                sub_invocations: vec![env],
+            }),
+        ]);
+        YieldStrategyClient::new(env, strategy).strategy_deposit(&owner, asset, &amount);
+
+        let held = storage::read_strategy_principal(env, strategy, asset);
+        storage::write_strategy_principal(env, strategy, asset, held + amount);
+    }
+
+    /// Withdraw a job's principal from its strategy to `recipient`.
+    /// The yield accrued on that share of the strategy position is split by the job's
+    /// yield policy; returns the yield amount.
+    pub(crate) fn withdraw_principal(env: &Env, job: &Job, amount: i128, recipient: &Address) -> Result<i128, Error> {
+        let strategy = match &job.strategy {
+            YieldStrategy::NoYield => {
+                // Funds already sit in this contract when it is the recipient
+                if *recipient != env.current_contract_address() {
+                    token::TokenClient::new(env, &job.asset_address).transfer(
+                        &env.current_contract_address(),
+                        recipient,
+                        &amount,
+                    );
+                }
+                return Ok(0);
+            }
+            YieldStrategy::Contract(strategy) => strategy,
+        };
+
+        let owner = env.current_contract_address();
+        let strategy_client = YieldStrategyClient::new(env, strategy);
+
+        let held = storage::read_strategy_principal(env, strategy, &job.asset_address);
+        if held < amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        // The withdrawn principal takes what the index has gained since the job's snapshot
+        let mut yield_index = Self::sync_yield_index(env, strategy, &job.asset_address);
+        let snapshot = storage::read_yield_snapshot(env, &job.job_id);
+        let yield_share = (amount * (yield_index.index - snapshot).max(0) / YIELD_INDEX_SCALE)
+            .min(yield_index.accounted);
+        yield_index.accounted -= yield_share;
+        storage::write_yield_index(env, strategy, &job.asset_address, &yield_index);
+
+        strategy_client.strategy_withdraw_principal(&owner, &job.asset_address, &amount, recipient);
+        storage::write_strategy_principal(env, strategy, &job.asset_address, held - amount);
+
+        Self::distribute_yield(env, job, strategy, yield_share)
+    }
+
+    /// Spread the yield a strategy position accrued since its principal last moved over
+    /// that principal, leaving out yield already owed to particular jobs.
+    /// Returns the updated index.
+    fn sync_yield_index(env: &Env, strategy: &Address, asset: &Address) -> YieldIndex {
+        let mut yield_index = storage::read_yield_index(env, strategy, asset);
+        let held = storage::read_strategy_principal(env, strategy, asset);
+        if held <= 0 {
+            return yield_index;
+        }
+
+        let balance = YieldStrategyClient::new(env, strategy)
+            .strategy_preview_balance(&env.current_contract_address(), asset);
+        let reserved = storage::read_strategy_unclaimed_yield(env, strategy, asset);
+        let accrued = (balance - held - reserved).max(0);
+
+        // Only what the index can express is accounted; the remainder waits for later
+        let increase = (accrued - yield_index.accounted).max(0) * YIELD_INDEX_SCALE / held;
+        if increase > 0 {
+            yield_index.index += increase;
+            yield_index.accounted += increase * held / YIELD_INDEX_SCALE;
+            storage::write_yield_index(env, strategy, asset, &yield_index);
+        }
+        yield_index
+    }
+}
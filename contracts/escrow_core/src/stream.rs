@@ -0,0 +1,206 @@
+// Streaming retainer jobs for escrow_core
+// A streaming job has no milestones: its funded amount vests linearly between a start
+// and an end time, and the freelancer withdraws whatever has vested at any moment.
+// The unvested balance stays in the job's yield strategy. The client can stop the
+// stream, which refunds the unvested part; what had vested remains withdrawable.
+// Withdrawals are paid like milestones, with milestone id `STREAM_PAYOUT_ID`. Vesting
+// counts from `start_time` even while the stream awaits the freelancer's acceptance.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus, Milestone,
+};
+
+/// Milestone id used in payout events for streamed withdrawals
+pub const STREAM_PAYOUT_ID: u32 = 0;
+
+/// Vesting terms of a streaming job
+#[contracttype]
+#[derive(Clone)]
+pub struct Stream {
+    pub start_time: u64,
+    pub end_time: u64,
+    pub withdrawn: i128,
+    pub stopped_at: Option<u64>,
+}
+
+impl Stream {
+    /// Amount of `total_amount` vested at `now`
+    pub fn vested_amount(&self, total_amount: i128, now: u64) -> i128 {
+        let until = now.min(self.stopped_at.unwrap_or(self.end_time)).min(self.end_time);
+        if until <= self.start_time {
+            return 0;
+        }
+        total_amount * (until - self.start_time) as i128 / (self.end_time - self.start_time) as i128
+    }
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Create a retainer job whose `total_amount` vests linearly from `start_time`
+    /// to `end_time`, held in the asset's default yield strategy meanwhile
+    pub fn create_stream_job(
+        env: Env,
+        client: Address,
+        freelancer: Address,
+        total_amount: i128,
+        asset_address: Address,
+        start_time: u64,
+        end_time: u64,
+    ) -> Result<BytesN<32>, Error> {
+        client.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+        Self::require_current_schema(&env)?;
+
+        if total_amount <= 0 || end_time <= start_time || end_time <= env.ledger().timestamp() {
+            return Err(Error::InvalidStream);
+        }
+        let strategy = Self::resolve_strategy(&env, &asset_address, None)?;
+
+        // No milestones to review
+        let job_id = Self::fund_job(
+            &env,
+            client,
+            freelancer,
+            total_amount,
+            asset_address,
+            Vec::<Milestone>::new(&env),
+            0,
+            strategy,
+        );
+
+        storage::write_stream(
+            &env,
+            &job_id,
+            &Stream {
+                start_time,
+                end_time,
+                withdrawn: 0,
+                stopped_at: None,
+            },
+        );
+
+        Ok(job_id)
+    }
+
+    /// Freelancer withdraws everything vested so far.
+    /// Returns the amount delivered to the freelancer.
+    pub fn withdraw_streamed(env: Env, job_id: BytesN<32>) -> Result<i128, Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let mut stream = storage::read_stream(&env, &job_id).ok_or(Error::NotAStream)?;
+
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        let vested = stream.vested_amount(job.total_amount, env.ledger().timestamp());
+        let amount = vested - stream.withdrawn;
+        if amount <= 0 {
+            return Ok(0);
+        }
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        if ledger.balance() < amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        let (yield_amount, delivered) = Self::pay_freelancer(&env, &job, STREAM_PAYOUT_ID, amount, None)?;
+
+        ledger.released += amount;
+        ledger.yield_attributed += yield_amount;
+        job.yield_earned += yield_amount;
+        storage::write_ledger(&env, &job_id, &ledger);
+        Self::adjust_total_escrowed(&env, -amount);
+
+        stream.withdrawn += amount;
+        Self::update_stream_status(&mut job, &stream);
+        storage::write_stream(&env, &job_id, &stream);
+        storage::write_job(&env, &job);
+
+        Ok(delivered)
+    }
+
+    /// Client stops a stream: the unvested balance is refunded with its yield and the
+    /// freelancer can still withdraw what had vested
+    pub fn stop_stream(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        let stream = storage::read_stream(&env, &job_id).ok_or(Error::NotAStream)?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Active {
+            return Err(Error::JobNotActive);
+        }
+
+        Self::stop(&env, &mut job, stream)
+    }
+
+    /// Get the vesting terms of a streaming job
+    pub fn get_stream(env: Env, job_id: BytesN<32>) -> Result<Stream, Error> {
+        storage::read_stream(&env, &job_id).ok_or(Error::NotAStream)
+    }
+
+    /// Amount the freelancer of a streaming job can withdraw now
+    pub fn get_withdrawable(env: Env, job_id: BytesN<32>) -> Result<i128, Error> {
+        let job = Self::get_job(env.clone(), job_id.clone())?;
+        let stream = storage::read_stream(&env, &job_id).ok_or(Error::NotAStream)?;
+
+        Ok(stream.vested_amount(job.total_amount, env.ledger().timestamp()) - stream.withdrawn)
+    }
+}
+
+impl EscrowCore {
+    /// Stop an active stream now and refund its unvested balance to the client.
+    /// The caller has checked authorization.
+    pub(crate) fn stop(env: &Env, job: &mut Job, mut stream: Stream) -> Result<(), Error> {
+        if stream.stopped_at.is_some() {
+            return Err(Error::JobNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = stream.vested_amount(job.total_amount, now);
+        let refund_amount = job.total_amount - vested;
+        stream.stopped_at = Some(now);
+
+        let mut ledger = storage::read_ledger(env, &job.job_id);
+        Self::refund_client(env, job, &mut ledger, refund_amount)?;
+        storage::write_ledger(env, &job.job_id, &ledger);
+
+        Self::update_stream_status(job, &stream);
+        storage::write_stream(env, &job.job_id, &stream);
+        storage::write_job(env, job);
+
+        events::StreamStopped {
+            job_id: job.job_id.clone(),
+            vested_amount: vested,
+            refund_amount,
+        }
+        .publish(env);
+
+        Ok(())
+    }
+
+    /// Close a stream once nothing more can be withdrawn from it: completed if it
+    /// vested in full, cancelled if it was stopped early
+    fn update_stream_status(job: &mut Job, stream: &Stream) {
+        let final_amount = match stream.stopped_at {
+            Some(stopped_at) => stream.vested_amount(job.total_amount, stopped_at),
+            None => job.total_amount,
+        };
+        if stream.withdrawn < final_amount {
+            return;
+        }
+
+        job.status = if final_amount == job.total_amount {
+            JobStatus::Completed
+        } else {
+            JobStatus::Cancelled
+        };
+    }
+}
@@ -0,0 +1,107 @@
+// Freelancer acceptance of job terms for escrow_core
+// A job the client creates for a named freelancer is funded but stays `Pending` until
+// the freelancer accepts the hash of the agreed terms. Until the acceptance deadline
+// the client cannot pull the funds back; after it, an unaccepted job can be withdrawn
+// with a full refund. Without `terms` in the `create_job` options, and for hourly and
+// streaming jobs, the hash is all zeros and the deadline `DEFAULT_ACCEPTANCE_PERIOD`
+// after creation. Jobs hired from a posting start active: the freelancer proposed them.
+
+use soroban_sdk::{contractimpl, contracttype, BytesN, Env};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, JobStatus,
+};
+
+/// Terms a freelancer must accept: `terms_hash` commits to the off-chain terms and
+/// `accept_by` is the deadline for accepting them
+#[contracttype]
+#[derive(Clone)]
+pub struct JobTerms {
+    pub terms_hash: BytesN<32>,
+    pub accept_by: u64,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Freelancer accepts a pending job's terms, making it active
+    pub fn accept_job(env: Env, job_id: BytesN<32>, terms_hash: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        job.freelancer.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Pending {
+            return Err(Error::JobNotPending);
+        }
+        if env.ledger().timestamp() >= job.accept_by {
+            return Err(Error::AcceptanceExpired);
+        }
+        if terms_hash != job.terms_hash {
+            return Err(Error::TermsMismatch);
+        }
+
+        job.status = JobStatus::Active;
+        storage::write_job(&env, &job);
+
+        events::JobAccepted {
+            job_id,
+            freelancer: job.freelancer,
+            terms_hash,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+
+    /// Client withdraws a job the freelancer did not accept in time; the escrow is
+    /// refunded in full with its yield
+    pub fn withdraw_unaccepted_job(env: Env, job_id: BytesN<32>) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+
+        job.client.require_auth();
+        storage::extend_instance_ttl(&env);
+
+        if job.status != JobStatus::Pending {
+            return Err(Error::JobNotPending);
+        }
+        if env.ledger().timestamp() < job.accept_by {
+            return Err(Error::AcceptanceNotExpired);
+        }
+
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        let refund_amount = ledger.balance();
+        Self::refund_client(&env, &mut job, &mut ledger, refund_amount)?;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        job.status = JobStatus::Cancelled;
+        storage::write_job(&env, &job);
+
+        events::JobWithdrawn {
+            job_id,
+            refund_amount,
+        }
+        .publish(&env);
+
+        Ok(())
+    }
+}
+
+impl EscrowCore {
+    /// Check the terms a new job is created with
+    pub(crate) fn validate_terms(env: &Env, terms: &JobTerms) -> Result<(), Error> {
+        if terms.accept_by <= env.ledger().timestamp() {
+            return Err(Error::InvalidExpiry);
+        }
+        Ok(())
+    }
+
+    /// Hold a newly funded job as `Pending` until its freelancer accepts `terms`
+    pub(crate) fn require_acceptance(env: &Env, job_id: &BytesN<32>, terms: JobTerms) -> Result<(), Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        job.status = JobStatus::Pending;
+        job.terms_hash = terms.terms_hash;
+        job.accept_by = terms.accept_by;
+        storage::write_job(env, &job);
+        Ok(())
+    }
+}
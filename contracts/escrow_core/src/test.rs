@@ -0,0 +1,1266 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger, MockAuth, MockAuthInvoke};
+use soroban_sdk::{contract, contractimpl, token, vec, Address, BytesN, Env, IntoVal, String, Symbol};
+
+/// Yield strategy that accrues 1% of every deposit as yield straight away, and after
+/// `set_rate` also `rate` per million of principal every second. `set_cap` limits how
+/// much yield a claim can pay, simulating a strategy that comes up short.
+#[contract]
+pub struct MockStrategy;
+
+#[contractimpl]
+impl MockStrategy {
+    pub fn strategy_deposit(env: Env, owner: Address, token: Address, amount: i128) {
+        owner.require_auth();
+        Self::accrue(&env);
+        token::Client::new(&env, &token).transfer(&owner, env.current_contract_address(), &amount);
+        Self::add(&env, "principal", amount);
+        Self::add(&env, "yield", amount / 100);
+    }
+
+    pub fn strategy_withdraw_principal(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> i128 {
+        owner.require_auth();
+        Self::accrue(&env);
+        assert!(Self::read(&env, "principal") >= amount);
+        Self::add(&env, "principal", -amount);
+        token::Client::new(&env, &token).transfer(&env.current_contract_address(), &recipient, &amount);
+        amount
+    }
+
+    pub fn strategy_claim_yield(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> i128 {
+        owner.require_auth();
+        Self::accrue(&env);
+        let cap: i128 = env.storage().instance().get(&Symbol::new(&env, "cap")).unwrap_or(i128::MAX);
+        let paid = amount.min(Self::read(&env, "yield")).min(cap);
+        Self::add(&env, "yield", -paid);
+        if paid > 0 {
+            token::Client::new(&env, &token).transfer(&env.current_contract_address(), &recipient, &paid);
+        }
+        paid
+    }
+
+    pub fn strategy_preview_balance(env: Env, _owner: Address, _token: Address) -> i128 {
+        Self::read(&env, "principal") + Self::read(&env, "yield") + Self::pending(&env)
+    }
+
+    /// Legacy YieldHarvester withdrawal paying 1% yield
+    pub fn withdraw_to(
+        env: Env,
+        _owner: Address,
+        amount: i128,
+        principal_recipient: Address,
+        yield_recipient: Address,
+    ) -> i128 {
+        let token: Address = env.storage().instance().get(&Symbol::new(&env, "legacy_token")).unwrap();
+        let token = token::Client::new(&env, &token);
+        token.transfer(&env.current_contract_address(), &principal_recipient, &amount);
+        token.transfer(&env.current_contract_address(), &yield_recipient, &(amount / 100));
+        amount + amount / 100
+    }
+
+    pub fn set_rate(env: Env, rate: i128) {
+        Self::accrue(&env);
+        env.storage().instance().set(&Symbol::new(&env, "rate"), &rate);
+    }
+
+    pub fn set_cap(env: Env, cap: i128) {
+        env.storage().instance().set(&Symbol::new(&env, "cap"), &cap);
+    }
+
+    pub fn set_legacy_token(env: Env, token: Address) {
+        env.storage().instance().set(&Symbol::new(&env, "legacy_token"), &token);
+    }
+}
+
+impl MockStrategy {
+    fn read(env: &Env, key: &str) -> i128 {
+        env.storage().instance().get(&Symbol::new(env, key)).unwrap_or(0)
+    }
+
+    fn add(env: &Env, key: &str, delta: i128) {
+        let value = Self::read(env, key) + delta;
+        env.storage().instance().set(&Symbol::new(env, key), &value);
+    }
+
+    /// Yield accrued on the principal since the last accrual
+    fn pending(env: &Env) -> i128 {
+        let accrued_at: u64 = env.storage().instance().get(&Symbol::new(env, "accrued_at")).unwrap_or(0);
+        let elapsed = (env.ledger().timestamp() - accrued_at) as i128;
+        Self::read(env, "principal") * Self::read(env, "rate") * elapsed / 1_000_000
+    }
+
+    fn accrue(env: &Env) {
+        Self::add(env, "yield", Self::pending(env));
+        env.storage().instance().set(&Symbol::new(env, "accrued_at"), &env.ledger().timestamp());
+    }
+}
+
+/// Router converting at `rate` that quotes `quote`, both scaled by `RATE_SCALE`, and
+/// fails below the minimum
+#[contract]
+pub struct MockRouter;
+
+#[contractimpl]
+impl MockRouter {
+    pub fn convert_and_send(
+        env: Env,
+        from_asset: Address,
+        to_asset: Address,
+        amount: i128,
+        recipient: Address,
+        min_amount_out: i128,
+    ) -> i128 {
+        let escrow: Address = env.storage().instance().get(&Symbol::new(&env, "escrow")).unwrap();
+        escrow.require_auth();
+        token::Client::new(&env, &from_asset).transfer(&escrow, env.current_contract_address(), &amount);
+
+        let rate: i128 = env.storage().instance().get(&Symbol::new(&env, "rate")).unwrap();
+        let converted_amount = amount * rate / RATE_SCALE;
+        assert!(converted_amount >= min_amount_out);
+        token::Client::new(&env, &to_asset).transfer(&env.current_contract_address(), &recipient, &converted_amount);
+        converted_amount
+    }
+
+    pub fn get_exchange_rate(env: Env, _from_asset: Address, _to_asset: Address) -> i128 {
+        env.storage().instance().get(&Symbol::new(&env, "quote")).unwrap()
+    }
+
+    pub fn setup(env: Env, escrow: Address, rate: i128, quote: i128) {
+        env.storage().instance().set(&Symbol::new(&env, "escrow"), &escrow);
+        env.storage().instance().set(&Symbol::new(&env, "rate"), &rate);
+        env.storage().instance().set(&Symbol::new(&env, "quote"), &quote);
+    }
+}
+
+struct Setup<'a> {
+    escrow: EscrowCoreClient<'a>,
+    token: token::Client<'a>,
+    strategy: MockStrategyClient<'a>,
+    router: MockRouterClient<'a>,
+    client: Address,
+    freelancer: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    let asset = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    let sac = token::StellarAssetClient::new(env, &asset);
+
+    // The strategy holds reserves to pay yield from
+    let strategy = MockStrategyClient::new(env, &env.register(MockStrategy, ()));
+    sac.mint(&strategy.address, &1_000_000);
+    let router = MockRouterClient::new(env, &env.register(MockRouter, ()));
+
+    let escrow = EscrowCoreClient::new(env, &env.register(EscrowCore, ()));
+    escrow.initialize(
+        &Address::generate(env),
+        &strategy.address,
+        &router.address,
+        &String::from_str(env, "USDC"),
+        &0,
+    );
+    router.setup(&escrow.address, &(83 * RATE_SCALE), &(83 * RATE_SCALE));
+
+    let client = Address::generate(env);
+    sac.mint(&client, &10_000);
+
+    Setup {
+        escrow,
+        token: token::Client::new(env, &asset),
+        strategy,
+        router,
+        client,
+        freelancer: Address::generate(env),
+    }
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+/// Freelancer accepts a job created without terms
+fn accept(env: &Env, s: &Setup, job_id: &BytesN<32>) {
+    s.escrow.accept_job(job_id, &hash(env, 0));
+}
+
+fn approve(env: &Env, s: &Setup, job_id: &BytesN<32>, milestone_id: u32) {
+    s.escrow.submit_proof(job_id, &milestone_id, &String::from_str(env, "ipfs://proof"), &hash(env, 1));
+    s.escrow.approve_milestone(job_id, &milestone_id);
+}
+
+fn with_milestones(milestones: Vec<MilestoneSpec>, review_period: u64) -> JobOptions {
+    let mut options = JobOptions::new(milestones.env());
+    options.milestones = Some(milestones);
+    options.review_period = Some(review_period);
+    options
+}
+
+fn milestone_specs(env: &Env, amounts: &[i128]) -> Vec<MilestoneSpec> {
+    let mut specs = Vec::new(env);
+    for amount in amounts {
+        specs.push_back(MilestoneSpec {
+            amount: *amount,
+            title_hash: hash(env, 2),
+            due_date: 0,
+        });
+    }
+    specs
+}
+
+#[test]
+fn job_ledger_tracks_funding_releases_and_refunds() {
+    let env = Env::default();
+    let s = setup(&env);
+    let paid = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &paid);
+    let cancelled = s.escrow.create_job(&s.client, &s.freelancer, &300, &s.token.address, &3, &JobOptions::new(&env));
+    accept(&env, &s, &cancelled);
+    assert_eq!(s.escrow.get_total_escrowed(), 1_300);
+
+    approve(&env, &s, &paid, 1);
+    s.escrow.release_payment(&paid, &1);
+    s.escrow.cancel_job(&cancelled);
+
+    let ledger = s.escrow.get_job_ledger(&paid);
+    assert_eq!((ledger.funded, ledger.released, ledger.refunded), (1_000, 500, 0));
+    assert_eq!(s.escrow.get_job_balance(&paid), 500);
+    assert_eq!(s.escrow.get_job_ledger(&cancelled).refunded, 300);
+    assert_eq!(s.escrow.get_job_balance(&cancelled), 0);
+    assert_eq!(s.escrow.get_total_escrowed(), 500);
+    assert_eq!(s.token.balance(&s.freelancer), 500);
+}
+
+#[test]
+fn strategy_round_trip_returns_principal_and_yield() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    assert_eq!(s.token.balance(&s.strategy.address), 1_001_000);
+    assert_eq!(s.strategy.strategy_preview_balance(&s.escrow.address, &s.token.address), 1_010);
+
+    let before = s.token.balance(&s.client);
+    s.escrow.cancel_job(&job_id);
+
+    assert_eq!(s.token.balance(&s.client) - before, 1_010);
+    assert_eq!(s.escrow.get_job(&job_id).yield_earned, 10);
+    assert_eq!(s.strategy.strategy_preview_balance(&s.escrow.address, &s.token.address), 0);
+}
+
+#[test]
+fn cancel_waits_for_milestones_in_review() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+
+    s.escrow.submit_proof(&job_id, &1, &String::from_str(&env, "ipfs://proof"), &hash(&env, 1));
+    assert_eq!(s.escrow.try_cancel_job(&job_id), Err(Ok(Error::MilestonesInReview)));
+    s.escrow.approve_milestone(&job_id, &1);
+    assert_eq!(s.escrow.try_cancel_job(&job_id), Err(Ok(Error::MilestonesInReview)));
+
+    s.escrow.release_payment(&job_id, &1);
+    s.escrow.cancel_job(&job_id);
+    assert_eq!(s.token.balance(&s.freelancer), 500);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 500);
+}
+
+#[test]
+fn finalize_job_pays_unclaimed_yield_once() {
+    let env = Env::default();
+    let s = setup(&env);
+    let short = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &short);
+    let other = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &other);
+    assert_eq!(s.escrow.try_finalize_job(&short), Err(Ok(Error::JobNotFinished)));
+
+    // The strategy pays no yield when the first job is cancelled
+    s.strategy.set_cap(&0);
+    s.escrow.cancel_job(&short);
+    s.strategy.set_cap(&i128::MAX);
+
+    // The yield still owed to the first job is not paid out to the second. The first
+    // job's deposit had earned its 1% before the second joined the position.
+    let before = s.token.balance(&s.client);
+    s.escrow.cancel_job(&other);
+    assert_eq!(s.token.balance(&s.client) - before, 1_005);
+
+    assert_eq!(s.escrow.finalize_job(&short), 15);
+    assert_eq!(s.token.balance(&s.client) - before, 1_020);
+    let job = s.escrow.get_job(&short);
+    assert!(job.status == JobStatus::Settled);
+    assert_eq!(job.yield_earned, 15);
+    assert_eq!(s.escrow.try_finalize_job(&short), Err(Ok(Error::JobNotFinished)));
+}
+
+#[test]
+fn profile_conversion_falls_back_to_the_job_asset() {
+    let env = Env::default();
+    let s = setup(&env);
+    let inr = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    token::StellarAssetClient::new(&env, &inr).mint(&s.router.address, &1_000_000);
+    s.escrow.set_payout_profile(
+        &s.freelancer,
+        &PayoutProfile {
+            preferred_asset: Some(inr.clone()),
+            max_slippage_bps: 0,
+            splits: Vec::new(&env),
+        },
+    );
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &2_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    approve(&env, &s, &job_id, 1);
+    approve(&env, &s, &job_id, 2);
+
+    // The router quotes 83 but only delivers 80: paid in the job's asset instead
+    s.router.setup(&s.escrow.address, &(80 * RATE_SCALE), &(83 * RATE_SCALE));
+    s.escrow.release_payment(&job_id, &1);
+    assert_eq!(s.token.balance(&s.freelancer), 1_000);
+    assert_eq!(token::Client::new(&env, &inr).balance(&s.freelancer), 0);
+
+    // A conversion the caller asks for still has to succeed
+    assert_eq!(
+        s.escrow.try_release_payment_with_conversion(&job_id, &2, &inr, &83_000, &0),
+        Err(Ok(Error::ConversionFailed))
+    );
+    s.router.setup(&s.escrow.address, &(83 * RATE_SCALE), &(83 * RATE_SCALE));
+    s.escrow.release_payment(&job_id, &2);
+    assert_eq!(token::Client::new(&env, &inr).balance(&s.freelancer), 83_000);
+}
+
+#[test]
+fn disputes_pass_over_a_registered_party() {
+    let env = Env::default();
+    let s = setup(&env);
+    let arbiter = Address::generate(&env);
+    s.escrow.add_arbiter(&s.client);
+    s.escrow.add_arbiter(&arbiter);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+
+    s.escrow.raise_dispute(&s.freelancer, &job_id, &1, &hash(&env, 9));
+    assert_eq!(s.escrow.get_dispute(&job_id, &1).arbiter, arbiter);
+
+    s.escrow.resolve_dispute(&job_id, &1, &300);
+    assert_eq!(s.token.balance(&s.freelancer), 300);
+    assert_eq!(s.escrow.get_job_balance(&job_id), 500);
+}
+
+#[test]
+fn organization_changes_need_member_signatures() {
+    let env = Env::default();
+    let s = setup(&env);
+    let (a, b, c) = (Address::generate(&env), Address::generate(&env), Address::generate(&env));
+    let members = vec![&env, a.clone(), b.clone(), c.clone()];
+    let no_signers = Vec::<Address>::new(&env);
+    s.escrow.set_organization(&s.client, &members, &2, &no_signers);
+
+    assert_eq!(
+        s.escrow.try_clear_organization(&s.client, &no_signers),
+        Err(Ok(Error::OrganizationApprovalRequired))
+    );
+    assert_eq!(
+        s.escrow.try_clear_organization(&s.client, &vec![&env, a.clone(), a.clone()]),
+        Err(Ok(Error::AlreadySigned))
+    );
+    assert_eq!(
+        s.escrow.try_clear_organization(&s.client, &vec![&env, a.clone(), s.freelancer.clone()]),
+        Err(Ok(Error::NotOrganizationMember))
+    );
+    s.escrow.clear_organization(&s.client, &vec![&env, a, b]);
+}
+
+#[test]
+fn postings_cap_new_proposals() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.post_job(
+        &s.client,
+        &1_000,
+        &s.token.address,
+        &milestone_specs(&env, &[1_000]),
+        &3_600,
+        &1_000,
+    );
+    for _ in 0..MAX_PROPOSALS {
+        s.escrow.submit_proposal(&job_id, &Address::generate(&env), &900, &3_600, &hash(&env, 4));
+    }
+
+    assert_eq!(
+        s.escrow.try_submit_proposal(&job_id, &s.freelancer, &900, &3_600, &hash(&env, 4)),
+        Err(Ok(Error::TooManyProposals))
+    );
+    assert_eq!(s.escrow.get_posting(&job_id).proposals.len(), MAX_PROPOSALS);
+}
+
+#[test]
+fn legacy_contract_migrates_under_the_legacy_admin() {
+    let env = Env::default();
+    let s = setup(&env);
+    s.strategy.set_legacy_token(&s.token.address);
+
+    // Rewrite the contract into the version 1 layout: no admin, one job in instance storage
+    let mut job_id_array = [0u8; 32];
+    job_id_array[28..].copy_from_slice(&1u32.to_be_bytes());
+    let job_id = BytesN::from_array(&env, &job_id_array);
+    env.as_contract(&s.escrow.address, || {
+        let storage = env.storage().instance();
+        storage.remove(&Symbol::new(&env, "schema_version"));
+        storage.remove(&Symbol::new(&env, "admin"));
+        storage.set(&Symbol::new(&env, "job_counter"), &1u32);
+
+        let mut milestones = Vec::new(&env);
+        for milestone_id in 1..=3 {
+            milestones.push_back(crate::migration::MilestoneV1 {
+                milestone_id,
+                amount: 33,
+                proof_url: String::from_str(&env, ""),
+                status: MilestoneStatus::Pending,
+            });
+        }
+        let legacy = crate::migration::JobV1 {
+            job_id: job_id.clone(),
+            client: s.client.clone(),
+            freelancer: s.freelancer.clone(),
+            total_amount: 100,
+            asset_address: s.token.address.clone(),
+            milestones,
+            status: JobStatus::Active,
+            created_at: 0,
+            yield_earned: 0,
+        };
+        storage.set(&job_id, &legacy);
+    });
+
+    // Nobody but the legacy admin can start the migration and take over the contract
+    let attacker = Address::generate(&env);
+    env.mock_auths(&[MockAuth {
+        address: &attacker,
+        invoke: &MockAuthInvoke {
+            contract: &s.escrow.address,
+            fn_name: "migrate",
+            args: (10u32,).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(s.escrow.try_migrate(&10).is_err());
+    assert_eq!(s.escrow.try_get_admin(), Err(Ok(Error::NotInitialized)));
+    assert!(matches!(s.escrow.try_get_job(&job_id), Err(Ok(Error::MigrationPending))));
+    assert!(matches!(s.escrow.try_get_client_jobs(&s.client, &0, &10), Err(Ok(Error::MigrationPending))));
+
+    env.mock_all_auths();
+    let legacy_admin = Address::from_str(&env, crate::migration::LEGACY_ADMIN.unwrap());
+    assert_eq!(s.escrow.migrate(&10), 0);
+    assert!(env.auths().iter().any(|(signer, _)| *signer == legacy_admin));
+    assert_eq!(s.escrow.get_admin(), legacy_admin);
+
+    assert_eq!(s.escrow.get_schema_version(), SCHEMA_VERSION);
+    let job = s.escrow.get_job(&job_id);
+    assert_eq!(job.milestones.get(2).unwrap().amount, 34);
+    assert_eq!(job.yield_earned, 1);
+    assert_eq!(s.escrow.get_job_balance(&job_id), 100);
+    assert_eq!(s.escrow.get_client_jobs(&s.client, &0, &10).len(), 1);
+    assert_eq!(s.escrow.get_freelancer_job_count(&s.freelancer), 1);
+}
+
+#[test]
+fn initialize_records_configuration() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    assert_eq!(s.escrow.get_liquidity_router(), s.router.address);
+    assert_eq!(s.escrow.get_usdc_token(), String::from_str(&env, "USDC"));
+    assert_eq!(
+        s.escrow.try_initialize(
+            &s.client,
+            &s.strategy.address,
+            &s.router.address,
+            &String::from_str(&env, "USDC"),
+            &0
+        ),
+        Err(Ok(Error::AlreadyInitialized))
+    );
+}
+
+#[test]
+fn party_job_lists_are_paginated() {
+    let env = Env::default();
+    let s = setup(&env);
+    let other_freelancer = Address::generate(&env);
+    let mut job_ids = Vec::new(&env);
+    for i in 0..5 {
+        let freelancer = if i % 2 == 0 { &s.freelancer } else { &other_freelancer };
+        job_ids.push_back(s.escrow.create_job(&s.client, freelancer, &100, &s.token.address, &1, &JobOptions::new(&env)));
+    }
+
+    assert_eq!(s.escrow.get_client_job_count(&s.client), 5);
+    let page = s.escrow.get_client_jobs(&s.client, &3, &10);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().job_id, job_ids.get(3).unwrap());
+    assert_eq!(s.escrow.get_client_jobs(&s.client, &5, &10).len(), 0);
+
+    let page = s.escrow.get_freelancer_jobs(&s.freelancer, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(1).unwrap().job_id, job_ids.get(2).unwrap());
+    assert_eq!(s.escrow.get_freelancer_job_count(&other_freelancer), 2);
+}
+
+#[test]
+fn freelancer_claims_after_the_review_window() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(
+        &s.client,
+        &s.freelancer,
+        &1_000,
+        &s.token.address,
+        &2,
+        &with_milestones(milestone_specs(&env, &[600, 400]), 3_600),
+    );
+    accept(&env, &s, &job_id);
+
+    assert_eq!(s.escrow.try_claim_milestone(&job_id, &1), Err(Ok(Error::MilestoneNotClaimable)));
+    s.escrow.submit_proof(&job_id, &1, &String::from_str(&env, "ipfs://proof"), &hash(&env, 1));
+
+    env.ledger().with_mut(|l| l.timestamp += 3_599);
+    assert_eq!(s.escrow.try_claim_milestone(&job_id, &1), Err(Ok(Error::ReviewPeriodNotOver)));
+
+    // The client stayed silent for the whole review period
+    env.ledger().with_mut(|l| l.timestamp += 1);
+    s.escrow.claim_milestone(&job_id, &1);
+    assert_eq!(s.token.balance(&s.freelancer), 600);
+    assert_eq!(s.escrow.get_job_balance(&job_id), 400);
+    assert_eq!(s.escrow.try_claim_milestone(&job_id, &1), Err(Ok(Error::MilestoneNotClaimable)));
+}
+
+#[test]
+fn client_reclaims_expired_milestones() {
+    let env = Env::default();
+    let s = setup(&env);
+    let mut specs = milestone_specs(&env, &[600, 400]);
+    for (index, due_date) in [(0, 1_000), (1, 0)] {
+        let mut spec = specs.get(index).unwrap();
+        spec.due_date = due_date;
+        specs.set(index, spec);
+    }
+    let job_id = s.escrow.create_job(
+        &s.client,
+        &s.freelancer,
+        &1_000,
+        &s.token.address,
+        &specs.len(),
+        &with_milestones(specs, DEFAULT_REVIEW_PERIOD),
+    );
+    accept(&env, &s, &job_id);
+    let before = s.token.balance(&s.client);
+
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + DEADLINE_GRACE_PERIOD - 1);
+    assert_eq!(s.escrow.try_reclaim_expired(&job_id, &1), Err(Ok(Error::MilestoneNotExpired)));
+    // Milestones without a due date never expire
+    env.ledger().with_mut(|l| l.timestamp += 1);
+    assert_eq!(s.escrow.try_reclaim_expired(&job_id, &2), Err(Ok(Error::MilestoneNotExpired)));
+
+    s.escrow.reclaim_expired(&job_id, &1);
+    let job = s.escrow.get_job(&job_id);
+    assert!(job.milestones.get(0).unwrap().status == MilestoneStatus::Expired);
+    assert!(job.status == JobStatus::Active);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 600);
+    // The refund carries the milestone's share of the strategy's 1% yield
+    assert_eq!(s.token.balance(&s.client) - before, 606);
+    assert_eq!(s.escrow.try_reclaim_expired(&job_id, &1), Err(Ok(Error::MilestoneNotReclaimable)));
+}
+
+#[test]
+fn proof_under_review_blocks_reclaiming() {
+    let env = Env::default();
+    let s = setup(&env);
+    let mut specs = milestone_specs(&env, &[1_000]);
+    let mut spec = specs.get(0).unwrap();
+    spec.due_date = 1_000;
+    specs.set(0, spec);
+    let job_id = s.escrow.create_job(
+        &s.client,
+        &s.freelancer,
+        &1_000,
+        &s.token.address,
+        &specs.len(),
+        &with_milestones(specs, DEFAULT_REVIEW_PERIOD),
+    );
+    accept(&env, &s, &job_id);
+
+    s.escrow.submit_proof(&job_id, &1, &String::from_str(&env, "ipfs://proof"), &hash(&env, 1));
+    env.ledger().with_mut(|l| l.timestamp = 1_000 + DEADLINE_GRACE_PERIOD);
+    assert_eq!(s.escrow.try_reclaim_expired(&job_id, &1), Err(Ok(Error::MilestoneNotReclaimable)));
+}
+
+fn mock_auth_of(env: &Env, s: &Setup, signer: &Address, fn_name: &'static str, args: soroban_sdk::Vec<soroban_sdk::Val>) {
+    env.mock_auths(&[MockAuth {
+        address: signer,
+        invoke: &MockAuthInvoke {
+            contract: &s.escrow.address,
+            fn_name,
+            args,
+            sub_invokes: &[],
+        },
+    }]);
+}
+
+/// Smallest module the host accepts as contract code: only the env meta section
+const EMPTY_CONTRACT_WASM: [u8; 40] = [
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, // magic and version
+    0x00, 0x1e, 0x11, // custom section, its size and the name length
+    b'c', b'o', b'n', b't', b'r', b'a', b'c', b't', b'e', b'n', b'v', b'm', b'e', b't', b'a', b'v', b'0',
+    0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x19, 0x00, 0x00, 0x00, 0x00, // interface version 25
+];
+
+#[test]
+fn pause_blocks_jobs_and_payouts_but_not_refunds() {
+    let env = Env::default();
+    let s = setup(&env);
+    let paid = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &paid);
+    let refunded = s.escrow.create_job(&s.client, &s.freelancer, &500, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &refunded);
+    approve(&env, &s, &paid, 1);
+
+    // Only the admin can pause
+    let attacker = Address::generate(&env);
+    mock_auth_of(&env, &s, &attacker, "pause", ().into_val(&env));
+    assert!(s.escrow.try_pause().is_err());
+    env.mock_all_auths();
+
+    s.escrow.pause();
+    assert!(s.escrow.is_paused());
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &100, &s.token.address, &1, &JobOptions::new(&env)),
+        Err(Ok(Error::ContractPaused))
+    );
+    assert_eq!(s.escrow.try_release_payment(&paid, &1), Err(Ok(Error::ContractPaused)));
+    s.escrow.cancel_job(&refunded);
+    assert_eq!(s.escrow.get_job_ledger(&refunded).refunded, 500);
+
+    s.escrow.unpause();
+    assert!(!s.escrow.is_paused());
+    s.escrow.release_payment(&paid, &1);
+    assert_eq!(s.token.balance(&s.freelancer), 1_000);
+}
+
+#[test]
+fn admin_moves_in_two_steps() {
+    let env = Env::default();
+    let s = setup(&env);
+    let admin = s.escrow.get_admin();
+    let new_admin = Address::generate(&env);
+
+    s.escrow.transfer_admin(&new_admin);
+    assert_eq!(s.escrow.get_admin(), admin);
+    assert_eq!(s.escrow.get_pending_admin(), Some(new_admin.clone()));
+
+    // The current admin cannot accept on the new admin's behalf
+    mock_auth_of(&env, &s, &admin, "accept_admin", ().into_val(&env));
+    assert!(s.escrow.try_accept_admin().is_err());
+
+    mock_auth_of(&env, &s, &new_admin, "accept_admin", ().into_val(&env));
+    s.escrow.accept_admin();
+    assert_eq!(s.escrow.get_admin(), new_admin);
+    assert_eq!(s.escrow.get_pending_admin(), None);
+    assert_eq!(s.escrow.try_accept_admin(), Err(Ok(Error::NoPendingAdmin)));
+
+    // The previous admin has lost its rights
+    mock_auth_of(&env, &s, &admin, "pause", ().into_val(&env));
+    assert!(s.escrow.try_pause().is_err());
+    mock_auth_of(&env, &s, &new_admin, "pause", ().into_val(&env));
+    s.escrow.pause();
+}
+
+#[test]
+fn only_the_admin_can_upgrade() {
+    let env = Env::default();
+    let s = setup(&env);
+    let admin = s.escrow.get_admin();
+    let wasm_hash = env
+        .deployer()
+        .upload_contract_wasm(soroban_sdk::Bytes::from_array(&env, &EMPTY_CONTRACT_WASM));
+
+    let attacker = Address::generate(&env);
+    mock_auth_of(&env, &s, &attacker, "upgrade", (wasm_hash.clone(),).into_val(&env));
+    assert!(s.escrow.try_upgrade(&wasm_hash).is_err());
+    assert_eq!(s.escrow.get_admin(), admin);
+
+    // The new code has no functions, so the old entrypoints are gone
+    mock_auth_of(&env, &s, &admin, "upgrade", (wasm_hash.clone(),).into_val(&env));
+    s.escrow.upgrade(&wasm_hash);
+    assert!(s.escrow.try_get_admin().is_err());
+}
+
+#[test]
+fn fees_are_snapshotted_per_client_and_sent_to_the_treasury() {
+    let env = Env::default();
+    let s = setup(&env);
+    let other_client = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.token.address).mint(&other_client, &10_000);
+    let treasury = Address::generate(&env);
+
+    assert_eq!(s.escrow.try_set_client_fee(&other_client, &100), Err(Ok(Error::TreasuryNotSet)));
+    assert_eq!(s.escrow.try_set_platform_fee(&(MAX_FEE_BPS + 1), &treasury), Err(Ok(Error::FeeTooHigh)));
+    s.escrow.set_platform_fee(&500, &treasury);
+    s.escrow.set_client_fee(&other_client, &100);
+
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let discounted = s.escrow.create_job(&other_client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &discounted);
+    assert_eq!(s.escrow.get_job(&job_id).fee_bps, 500);
+    assert_eq!(s.escrow.get_job(&discounted).fee_bps, 100);
+
+    // Later changes leave funded jobs on the rate they were created with
+    s.escrow.set_platform_fee(&MAX_FEE_BPS, &treasury);
+    s.escrow.clear_client_fee(&other_client);
+    assert_eq!(s.escrow.get_client_fee(&other_client), None);
+    let later = s.escrow.create_job(&other_client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &later);
+    assert_eq!(s.escrow.get_job(&later).fee_bps, MAX_FEE_BPS);
+
+    approve(&env, &s, &job_id, 1);
+    s.escrow.release_payment(&job_id, &1);
+    approve(&env, &s, &discounted, 1);
+    s.escrow.release_payment(&discounted, &1);
+
+    assert_eq!(s.token.balance(&s.freelancer), 950 + 990);
+    assert_eq!(s.token.balance(&treasury), 50 + 10);
+    assert_eq!(s.escrow.get_collected_fees(&s.token.address), 60);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).released, 1_000);
+}
+
+#[test]
+fn create_job_checks_its_options() {
+    let env = Env::default();
+    let s = setup(&env);
+
+    assert_eq!(
+        s.escrow.try_create_job(
+            &s.client,
+            &s.freelancer,
+            &1_000,
+            &s.token.address,
+            &3,
+            &with_milestones(milestone_specs(&env, &[600, 400]), 3_600),
+        ),
+        Err(Ok(Error::MilestoneCountMismatch))
+    );
+    let options = JobOptions {
+        yield_policy: vec![
+            &env,
+            YieldPolicy {
+                client_bps: 5_000,
+                freelancer_bps: 5_000,
+                platform_bps: 1,
+            },
+        ],
+        ..JobOptions::new(&env)
+    };
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &options),
+        Err(Ok(Error::InvalidYieldPolicy))
+    );
+    // Shares that wrap around to 10_000 are rejected too
+    let options = JobOptions {
+        yield_policy: vec![
+            &env,
+            YieldPolicy {
+                client_bps: u32::MAX,
+                freelancer_bps: BPS_DENOMINATOR + 1,
+                platform_bps: 0,
+            },
+        ],
+        ..JobOptions::new(&env)
+    };
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &options),
+        Err(Ok(Error::InvalidYieldPolicy))
+    );
+    let options = JobOptions {
+        strategy: vec![&env, YieldStrategy::NoYield, YieldStrategy::NoYield],
+        ..JobOptions::new(&env)
+    };
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &options),
+        Err(Ok(Error::InvalidJobOptions))
+    );
+
+    // Unset options take their defaults
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &3, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let job = s.escrow.get_job(&job_id);
+    assert_eq!(job.review_period, DEFAULT_REVIEW_PERIOD);
+    assert_eq!(job.milestones.get(2).unwrap().amount, 334);
+    assert_eq!(s.escrow.get_yield_policy(&job_id).client_bps, BPS_DENOMINATOR);
+    assert_eq!(s.escrow.get_milestone_payees(&job_id, &1).get(0).unwrap().recipient, s.freelancer);
+}
+
+#[test]
+fn payees_share_milestone_payouts_and_can_be_amended() {
+    let env = Env::default();
+    let s = setup(&env);
+    let designer = Address::generate(&env);
+    let split = vec![
+        &env,
+        Payee {
+            recipient: s.freelancer.clone(),
+            bps: 7_000,
+        },
+        Payee {
+            recipient: designer.clone(),
+            bps: 3_000,
+        },
+    ];
+    let payees_options = |milestone_id: u32, payees: Vec<Payee>| JobOptions {
+        payees: Some(Map::from_array(&env, [(milestone_id, payees)])),
+        ..JobOptions::new(&env)
+    };
+
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &payees_options(3, split.clone())),
+        Err(Ok(Error::MilestoneNotFound))
+    );
+    let mut short = split.clone();
+    short.pop_back();
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &payees_options(1, short)),
+        Err(Ok(Error::InvalidPayees))
+    );
+
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &payees_options(1, split));
+    accept(&env, &s, &job_id);
+    approve(&env, &s, &job_id, 1);
+    s.escrow.release_payment(&job_id, &1);
+    assert_eq!(s.token.balance(&s.freelancer), 350);
+    assert_eq!(s.token.balance(&designer), 150);
+
+    // The second milestone goes to the designer alone, with both parties' consent
+    let designer_only = vec![
+        &env,
+        Payee {
+            recipient: designer.clone(),
+            bps: BPS_DENOMINATOR,
+        },
+    ];
+    s.escrow.amend_milestone_payees(&job_id, &2, &designer_only);
+    let signers: Vec<Address> = Vec::from_iter(&env, env.auths().iter().map(|(signer, _)| signer.clone()));
+    assert!(signers.contains(&s.client) && signers.contains(&s.freelancer));
+    assert_eq!(
+        s.escrow.try_amend_milestone_payees(&job_id, &1, &designer_only),
+        Err(Ok(Error::MilestoneNotAmendable))
+    );
+
+    approve(&env, &s, &job_id, 2);
+    s.escrow.release_payment(&job_id, &2);
+    assert_eq!(s.token.balance(&s.freelancer), 350);
+    assert_eq!(s.token.balance(&designer), 650);
+}
+
+#[test]
+fn streams_vest_linearly_until_the_end_time() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.ledger().set_timestamp(1_000);
+
+    assert_eq!(
+        s.escrow.try_create_stream_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2_000, &2_000),
+        Err(Ok(Error::InvalidStream))
+    );
+    let job_id = s.escrow.create_stream_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1_000, &2_000);
+    accept(&env, &s, &job_id);
+    assert_eq!(s.escrow.get_withdrawable(&job_id), 0);
+
+    env.ledger().set_timestamp(1_250);
+    assert_eq!(s.escrow.get_withdrawable(&job_id), 250);
+    assert_eq!(s.escrow.withdraw_streamed(&job_id), 250);
+    assert_eq!(s.escrow.withdraw_streamed(&job_id), 0);
+    assert_eq!(s.token.balance(&s.freelancer), 250);
+
+    // Vesting stops at the end time
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(s.escrow.get_withdrawable(&job_id), 750);
+    s.escrow.withdraw_streamed(&job_id);
+    assert_eq!(s.token.balance(&s.freelancer), 1_000);
+    assert_eq!(s.escrow.get_stream(&job_id).withdrawn, 1_000);
+    assert!(s.escrow.get_job(&job_id).status == JobStatus::Completed);
+    assert_eq!(s.escrow.try_withdraw_streamed(&job_id), Err(Ok(Error::JobNotActive)));
+}
+
+#[test]
+fn stopped_streams_refund_the_unvested_part() {
+    let env = Env::default();
+    let s = setup(&env);
+    env.ledger().set_timestamp(1_000);
+    let job_id = s.escrow.create_stream_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1_000, &2_000);
+    accept(&env, &s, &job_id);
+
+    env.ledger().set_timestamp(1_400);
+    s.escrow.withdraw_streamed(&job_id);
+    env.ledger().set_timestamp(1_600);
+    let client_before = s.token.balance(&s.client);
+    s.escrow.stop_stream(&job_id);
+    assert!(s.token.balance(&s.client) - client_before >= 400);
+    assert_eq!(s.escrow.get_stream(&job_id).stopped_at, Some(1_600));
+    assert_eq!(s.escrow.try_stop_stream(&job_id), Err(Ok(Error::JobNotActive)));
+
+    // What had vested stays withdrawable, and nothing vests after the stop
+    env.ledger().set_timestamp(1_900);
+    assert_eq!(s.escrow.get_withdrawable(&job_id), 200);
+    s.escrow.withdraw_streamed(&job_id);
+    assert_eq!(s.token.balance(&s.freelancer), 600);
+    assert!(s.escrow.get_job(&job_id).status == JobStatus::Cancelled);
+
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &100, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    assert_eq!(s.escrow.try_stop_stream(&job_id), Err(Ok(Error::NotAStream)));
+}
+
+#[test]
+fn hourly_logs_are_billed_within_the_weekly_cap() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_hourly_job(&s.client, &s.freelancer, &1_000, &s.token.address, &10, &40, &3_600);
+    accept(&env, &s, &job_id);
+
+    let first = s.escrow.submit_time_log(&job_id, &0, &30, &hash(&env, 3));
+    assert_eq!(s.escrow.get_job(&job_id).milestones.get(0).unwrap().amount, 300);
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &0, &11, &hash(&env, 4)),
+        Err(Ok(Error::WeeklyCapExceeded))
+    );
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &0, &u32::MAX, &hash(&env, 4)),
+        Err(Ok(Error::HoursOverflow))
+    );
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &1, &10, &hash(&env, 4)),
+        Err(Ok(Error::InvalidTimeLog))
+    );
+    let second = s.escrow.submit_time_log(&job_id, &0, &10, &hash(&env, 4));
+    assert_eq!(s.escrow.get_hourly_terms(&job_id).logged_hours.get(0), Some(40));
+    assert_eq!(s.escrow.get_time_log(&job_id, &second).hours, 10);
+
+    // An approved log is paid at once, an unreviewed one after its review period
+    s.escrow.approve_milestone(&job_id, &first);
+    assert_eq!(s.escrow.settle_time_logs(&job_id), 300);
+    env.ledger().set_timestamp(3_600);
+    assert_eq!(s.escrow.settle_time_logs(&job_id), 100);
+    assert_eq!(s.token.balance(&s.freelancer), 400);
+
+    // Next week's hours count against a fresh cap, within what is left of the budget
+    env.ledger().set_timestamp(8 * 24 * 60 * 60);
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &1, &61, &hash(&env, 5)),
+        Err(Ok(Error::WeeklyCapExceeded))
+    );
+    s.escrow.submit_time_log(&job_id, &1, &40, &hash(&env, 5));
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &0, &1, &hash(&env, 6)),
+        Err(Ok(Error::WeeklyCapExceeded))
+    );
+}
+
+#[test]
+fn hourly_jobs_cap_time_logs() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_hourly_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &1_000, &3_600);
+    accept(&env, &s, &job_id);
+
+    for _ in 0..MAX_TIME_LOGS {
+        s.escrow.submit_time_log(&job_id, &0, &1, &hash(&env, 3));
+    }
+    assert_eq!(
+        s.escrow.try_submit_time_log(&job_id, &0, &1, &hash(&env, 3)),
+        Err(Ok(Error::TooManyTimeLogs))
+    );
+    assert_eq!(s.escrow.get_job(&job_id).milestones.len(), MAX_TIME_LOGS);
+}
+
+#[test]
+fn change_orders_amend_milestones_once_accepted() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let no_signers = Vec::<Address>::new(&env);
+
+    // Removing the last milestone frees its id for good
+    let order_id = s.escrow.propose_change_order(&job_id, &s.freelancer, &MilestoneChange::Remove(2), &no_signers);
+    assert_eq!(
+        s.escrow.try_accept_change_order(&job_id, &order_id, &s.freelancer, &no_signers),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.escrow.accept_change_order(&job_id, &order_id, &s.client, &no_signers);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 500);
+
+    // A client-side proposal brings its funding
+    let spec = milestone_specs(&env, &[300]).get(0).unwrap();
+    let order_id = s.escrow.propose_change_order(&job_id, &s.client, &MilestoneChange::Add(spec), &no_signers);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).funded, 1_300);
+    s.escrow.accept_change_order(&job_id, &order_id, &s.freelancer, &no_signers);
+    let job = s.escrow.get_job(&job_id);
+    assert_eq!(job.milestones.get(1).unwrap().milestone_id, 3);
+    assert_eq!(job.total_amount, 800);
+    assert_eq!(
+        s.escrow.try_accept_change_order(&job_id, &order_id, &s.freelancer, &no_signers),
+        Err(Ok(Error::ChangeOrderNotOpen))
+    );
+
+    let order_id = s.escrow.propose_change_order(&job_id, &s.freelancer, &MilestoneChange::Update(1, 400, 0), &no_signers);
+    s.escrow.accept_change_order(&job_id, &order_id, &s.client, &no_signers);
+    assert_eq!(s.escrow.get_job(&job_id).total_amount, 700);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 600);
+
+    // A rejected proposal hands its funding back
+    let spec = milestone_specs(&env, &[200]).get(0).unwrap();
+    let order_id = s.escrow.propose_change_order(&job_id, &s.client, &MilestoneChange::Add(spec), &no_signers);
+    s.escrow.reject_change_order(&job_id, &order_id, &s.freelancer);
+    let ledger = s.escrow.get_job_ledger(&job_id);
+    assert_eq!((ledger.funded, ledger.refunded), (1_500, 800));
+
+    let orders = s.escrow.get_change_orders(&job_id);
+    assert_eq!(orders.len(), 4);
+    assert!(orders.get(0).unwrap().status == ChangeOrderStatus::Accepted);
+    assert!(orders.get(3).unwrap().status == ChangeOrderStatus::Rejected);
+}
+
+#[test]
+fn removing_a_time_log_gives_its_hours_back() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_hourly_job(&s.client, &s.freelancer, &1_000, &s.token.address, &10, &40, &3_600);
+    accept(&env, &s, &job_id);
+    s.escrow.submit_time_log(&job_id, &0, &30, &hash(&env, 3));
+    let second = s.escrow.submit_time_log(&job_id, &0, &10, &hash(&env, 4));
+
+    let no_signers = Vec::<Address>::new(&env);
+    let order_id = s.escrow.propose_change_order(&job_id, &s.client, &MilestoneChange::Remove(second), &no_signers);
+    s.escrow.accept_change_order(&job_id, &order_id, &s.freelancer, &no_signers);
+    assert_eq!(s.escrow.get_hourly_terms(&job_id).logged_hours.get(0), Some(30));
+    assert!(matches!(s.escrow.try_get_time_log(&job_id, &second), Err(Ok(Error::MilestoneNotFound))));
+
+    let third = s.escrow.submit_time_log(&job_id, &0, &10, &hash(&env, 5));
+    assert_eq!(third, second + 1);
+}
+
+#[test]
+fn organization_change_orders_need_member_signatures() {
+    let env = Env::default();
+    let s = setup(&env);
+    let members = vec![&env, Address::generate(&env), Address::generate(&env), Address::generate(&env)];
+    s.escrow.set_organization(&s.client, &members, &2, &Vec::new(&env));
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let one = vec![&env, members.get(0).unwrap()];
+    let two = vec![&env, members.get(0).unwrap(), members.get(2).unwrap()];
+
+    let order_id = s.escrow.propose_change_order(&job_id, &s.freelancer, &MilestoneChange::Update(1, 600, 0), &Vec::new(&env));
+    assert_eq!(
+        s.escrow.try_accept_change_order(&job_id, &order_id, &s.client, &one),
+        Err(Ok(Error::OrganizationApprovalRequired))
+    );
+    assert_eq!(
+        s.escrow.try_accept_change_order(&job_id, &order_id, &s.client, &vec![&env, s.freelancer.clone(), members.get(1).unwrap()]),
+        Err(Ok(Error::NotOrganizationMember))
+    );
+    s.escrow.accept_change_order(&job_id, &order_id, &s.client, &two);
+    assert_eq!(s.escrow.get_job(&job_id).total_amount, 1_100);
+
+    let spec = milestone_specs(&env, &[100]).get(0).unwrap();
+    assert_eq!(
+        s.escrow.try_propose_change_order(&job_id, &s.client, &MilestoneChange::Add(spec.clone()), &one),
+        Err(Ok(Error::OrganizationApprovalRequired))
+    );
+    s.escrow.propose_change_order(&job_id, &s.client, &MilestoneChange::Add(spec), &two);
+}
+
+#[test]
+fn jobs_wait_for_the_freelancer_to_accept() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    let job = s.escrow.get_job(&job_id);
+    assert!(job.status == JobStatus::Pending);
+    assert_eq!(job.accept_by, DEFAULT_ACCEPTANCE_PERIOD);
+    assert_eq!(
+        s.escrow.try_submit_proof(&job_id, &1, &String::from_str(&env, "ipfs://proof"), &hash(&env, 1)),
+        Err(Ok(Error::JobNotActive))
+    );
+    assert_eq!(s.escrow.try_withdraw_unaccepted_job(&job_id), Err(Ok(Error::AcceptanceNotExpired)));
+    assert_eq!(s.escrow.try_accept_job(&job_id, &hash(&env, 7)), Err(Ok(Error::TermsMismatch)));
+    accept(&env, &s, &job_id);
+    assert!(s.escrow.get_job(&job_id).status == JobStatus::Active);
+    assert_eq!(s.escrow.try_accept_job(&job_id, &hash(&env, 0)), Err(Ok(Error::JobNotPending)));
+
+    // Terms chosen by the client replace the default hash and deadline
+    let mut options = JobOptions::new(&env);
+    options.terms = vec![&env, JobTerms { terms_hash: hash(&env, 7), accept_by: 0 }];
+    assert_eq!(
+        s.escrow.try_create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &options),
+        Err(Ok(Error::InvalidExpiry))
+    );
+    options.terms = vec![&env, JobTerms { terms_hash: hash(&env, 7), accept_by: 500 }];
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &options);
+    assert_eq!(s.escrow.try_accept_job(&job_id, &hash(&env, 0)), Err(Ok(Error::TermsMismatch)));
+    s.escrow.accept_job(&job_id, &hash(&env, 7));
+
+    // Hourly and streaming jobs wait as well; a hire from a posting does not
+    let hourly = s.escrow.create_hourly_job(&s.client, &s.freelancer, &1_000, &s.token.address, &10, &40, &3_600);
+    assert!(s.escrow.get_job(&hourly).status == JobStatus::Pending);
+    let stream = s.escrow.create_stream_job(&s.client, &s.freelancer, &1_000, &s.token.address, &0, &1_000);
+    assert!(s.escrow.get_job(&stream).status == JobStatus::Pending);
+    let posted = s.escrow.post_job(&s.client, &1_000, &s.token.address, &milestone_specs(&env, &[1_000]), &3_600, &1_000);
+    s.escrow.submit_proposal(&posted, &s.freelancer, &900, &3_600, &hash(&env, 4));
+    s.escrow.accept_proposal(&posted, &s.freelancer, &None);
+    assert!(s.escrow.get_job(&posted).status == JobStatus::Active);
+}
+
+#[test]
+fn unaccepted_jobs_are_withdrawn_after_the_deadline() {
+    let env = Env::default();
+    let s = setup(&env);
+    let mut options = JobOptions::new(&env);
+    options.terms = vec![&env, JobTerms { terms_hash: hash(&env, 7), accept_by: 1_000 }];
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &options);
+    assert_eq!(s.escrow.try_cancel_job(&job_id), Err(Ok(Error::JobNotActive)));
+
+    env.ledger().set_timestamp(1_000);
+    assert_eq!(s.escrow.try_accept_job(&job_id, &hash(&env, 7)), Err(Ok(Error::AcceptanceExpired)));
+    s.escrow.withdraw_unaccepted_job(&job_id);
+    assert!(s.escrow.get_job(&job_id).status == JobStatus::Cancelled);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 1_000);
+    // The refund carries the job's 1% yield
+    assert_eq!(s.token.balance(&s.client), 10_010);
+    assert_eq!(s.escrow.try_withdraw_unaccepted_job(&job_id), Err(Ok(Error::JobNotPending)));
+
+    let hourly = s.escrow.create_hourly_job(&s.client, &s.freelancer, &1_000, &s.token.address, &10, &40, &3_600);
+    env.ledger().set_timestamp(1_000 + DEFAULT_ACCEPTANCE_PERIOD);
+    s.escrow.withdraw_unaccepted_job(&hourly);
+    assert_eq!(s.escrow.get_total_escrowed(), 0);
+}
+
+#[test]
+fn reviewers_review_proof_but_cannot_move_funds() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let reviewer = Address::generate(&env);
+    let proof = String::from_str(&env, "ipfs://proof");
+
+    assert_eq!(
+        s.escrow.try_grant_role(&job_id, &s.freelancer, &JobRole::Reviewer),
+        Err(Ok(Error::InvalidRoleAccount))
+    );
+    assert_eq!(
+        s.escrow.try_review_milestone(&job_id, &1, &reviewer, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.escrow.grant_role(&job_id, &reviewer, &JobRole::Reviewer);
+    assert_eq!(
+        s.escrow.try_grant_role(&job_id, &reviewer, &JobRole::Reviewer),
+        Err(Ok(Error::RoleAlreadyGranted))
+    );
+
+    // The reviewer's own signature is what counts
+    s.escrow.submit_proof(&job_id, &1, &proof, &hash(&env, 1));
+    s.escrow.review_milestone(&job_id, &1, &reviewer, &false);
+    assert_eq!(env.auths().first().unwrap().0, reviewer);
+    assert!(s.escrow.get_job(&job_id).milestones.get(0).unwrap().status == MilestoneStatus::Pending);
+    s.escrow.submit_proof(&job_id, &1, &proof, &hash(&env, 2));
+    s.escrow.review_milestone(&job_id, &1, &reviewer, &true);
+    assert!(s.escrow.get_job(&job_id).milestones.get(0).unwrap().status == MilestoneStatus::Approved);
+
+    assert_eq!(
+        s.escrow.try_release_payment_as(&job_id, &1, &reviewer),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(s.escrow.try_cancel_job_as(&job_id, &reviewer), Err(Ok(Error::Unauthorized)));
+    assert_eq!(
+        s.escrow.try_propose_change_order(&job_id, &reviewer, &MilestoneChange::Remove(2), &Vec::new(&env)),
+        Err(Ok(Error::Unauthorized))
+    );
+
+    s.escrow.revoke_role(&job_id, &reviewer, &JobRole::Reviewer);
+    assert_eq!(s.escrow.get_job_roles(&job_id).len(), 0);
+    s.escrow.submit_proof(&job_id, &2, &proof, &hash(&env, 3));
+    assert_eq!(
+        s.escrow.try_review_milestone(&job_id, &2, &reviewer, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    assert_eq!(
+        s.escrow.try_revoke_role(&job_id, &reviewer, &JobRole::Reviewer),
+        Err(Ok(Error::RoleNotGranted))
+    );
+}
+
+#[test]
+fn payers_release_and_cancel_for_the_client() {
+    let env = Env::default();
+    let s = setup(&env);
+    let job_id = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &2, &JobOptions::new(&env));
+    accept(&env, &s, &job_id);
+    let payer = Address::generate(&env);
+    s.escrow.grant_role(&job_id, &payer, &JobRole::Payer);
+
+    s.escrow.submit_proof(&job_id, &1, &String::from_str(&env, "ipfs://proof"), &hash(&env, 1));
+    assert_eq!(
+        s.escrow.try_review_milestone(&job_id, &1, &payer, &true),
+        Err(Ok(Error::Unauthorized))
+    );
+    s.escrow.approve_milestone(&job_id, &1);
+    s.escrow.release_payment_as(&job_id, &1, &payer);
+    assert_eq!(s.token.balance(&s.freelancer), 500);
+
+    // Refunds go to the client, not the payer who cancelled
+    s.escrow.cancel_job_as(&job_id, &payer);
+    assert_eq!(s.token.balance(&payer), 0);
+    assert_eq!(s.escrow.get_job_ledger(&job_id).refunded, 500);
+    assert!(s.escrow.get_job(&job_id).status == JobStatus::Cancelled);
+}
+
+#[test]
+fn yield_is_shared_by_principal_and_time_held() {
+    let env = Env::default();
+    let s = setup(&env);
+    let late_client = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.token.address).mint(&late_client, &1_000);
+    s.strategy.set_rate(&10);
+
+    let early = s.escrow.create_job(&s.client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &early);
+
+    // The early job alone earns its deposit's 1% and the first 1_000 seconds
+    env.ledger().set_timestamp(1_000);
+    let late = s.escrow.create_job(&late_client, &s.freelancer, &1_000, &s.token.address, &1, &JobOptions::new(&env));
+    accept(&env, &s, &late);
+    let snapshot = s.escrow.get_yield_index(&s.strategy.address, &s.token.address).index;
+    assert_eq!(snapshot, 20 * YIELD_INDEX_SCALE / 1_000);
+
+    // After that both share the late deposit's 1% and the next 1_000 seconds equally
+    env.ledger().set_timestamp(2_000);
+    let before = s.token.balance(&s.client);
+    s.escrow.cancel_job(&early);
+    assert_eq!(s.token.balance(&s.client) - before, 1_035);
+    s.escrow.cancel_job(&late);
+    assert_eq!(s.token.balance(&late_client), 1_015);
+    assert_eq!(s.escrow.get_yield_index(&s.strategy.address, &s.token.address).accounted, 0);
+}
@@ -0,0 +1,174 @@
+// Yield allocation for escrow_core
+// A job's escrow earns yield in its strategy while it waits to be paid out. Each time
+// principal leaves the strategy, the yield accrued on it is split between the client,
+// the freelancer and the platform treasury by the job's policy, chosen at creation.
+// Jobs created without a policy send all of it to the client. What each party has
+// received so far is kept per job, as is any yield the strategy could not pay out
+// when asked. `finalize_job` sweeps that remainder once the job is over and closes
+// the job's books.
+use soroban_sdk::{contractimpl, contracttype, Address, BytesN, Env};
+
+use crate::{
+    events, storage, Error, EscrowCore, EscrowCoreArgs, EscrowCoreClient, Job, JobStatus,
+    YieldStrategy, YieldStrategyClient, BPS_DENOMINATOR,
+};
+
+/// Shares of a job's yield in basis points, adding up to 10_000
+#[contracttype]
+#[derive(Clone)]
+pub struct YieldPolicy {
+    pub client_bps: u32,
+    pub freelancer_bps: u32,
+    pub platform_bps: u32,
+}
+
+/// Yield a job has paid to each party so far
+#[contracttype]
+#[derive(Clone, Default)]
+pub struct YieldShares {
+    pub client: i128,
+    pub freelancer: i128,
+    pub platform: i128,
+}
+
+#[contractimpl]
+impl EscrowCore {
+    /// Get how a job's yield is split
+    pub fn get_yield_policy(env: Env, job_id: BytesN<32>) -> YieldPolicy {
+        storage::read_yield_policy(&env, &job_id)
+    }
+
+    /// Get the yield a job has paid to each party so far
+    pub fn get_yield_shares(env: Env, job_id: BytesN<32>) -> YieldShares {
+        storage::read_yield_shares(&env, &job_id)
+    }
+
+    /// Settle a completed or cancelled job: claim the yield still owed to it from its
+    /// strategy, pay it out by the job's policy and close the job for good. Anyone can
+    /// call this. Returns the yield swept.
+    pub fn finalize_job(env: Env, job_id: BytesN<32>) -> Result<i128, Error> {
+        let mut job = Self::get_job(env.clone(), job_id.clone())?;
+        storage::extend_instance_ttl(&env);
+        Self::require_not_paused(&env)?;
+
+        if job.status != JobStatus::Completed && job.status != JobStatus::Cancelled {
+            return Err(Error::JobNotFinished);
+        }
+        let mut ledger = storage::read_ledger(&env, &job_id);
+        if ledger.balance() != 0 {
+            return Err(Error::JobNotFinished);
+        }
+
+        // Whatever the strategy still cannot pay is recorded again as unclaimed
+        let owed = storage::read_unclaimed_yield(&env, &job_id);
+        let yield_swept = match &job.strategy {
+            YieldStrategy::Contract(strategy) if owed > 0 => {
+                storage::write_unclaimed_yield(&env, &job_id, 0);
+                Self::adjust_reserved_yield(&env, strategy, &job.asset_address, -owed);
+                Self::distribute_yield(&env, &job, strategy, owed)?
+            }
+            _ => 0,
+        };
+
+        ledger.yield_attributed += yield_swept;
+        storage::write_ledger(&env, &job_id, &ledger);
+
+        job.yield_earned += yield_swept;
+        job.status = JobStatus::Settled;
+        storage::write_job(&env, &job);
+
+        events::JobSettled {
+            job_id: job_id.clone(),
+            yield_swept,
+            yield_earned: job.yield_earned,
+            yield_unclaimed: storage::read_unclaimed_yield(&env, &job_id),
+        }
+        .publish(&env);
+
+        Ok(yield_swept)
+    }
+}
+
+impl EscrowCore {
+    /// Check the yield policy a new job is created with
+    pub(crate) fn validate_yield_policy(env: &Env, policy: &YieldPolicy) -> Result<(), Error> {
+        let total = policy
+            .client_bps
+            .checked_add(policy.freelancer_bps)
+            .and_then(|bps| bps.checked_add(policy.platform_bps))
+            .ok_or(Error::InvalidYieldPolicy)?;
+        if total != BPS_DENOMINATOR {
+            return Err(Error::InvalidYieldPolicy);
+        }
+        if policy.platform_bps > 0 && Self::get_treasury(env.clone()).is_none() {
+            return Err(Error::TreasuryNotSet);
+        }
+        Ok(())
+    }
+
+    /// Claim `amount` of yield from `strategy` for a job and pay it out by the job's
+    /// policy, the client taking any rounding remainder. What the strategy does not pay
+    /// is recorded as unclaimed. Returns the yield paid.
+    pub(crate) fn distribute_yield(env: &Env, job: &Job, strategy: &Address, amount: i128) -> Result<i128, Error> {
+        let policy = storage::read_yield_policy(env, &job.job_id);
+        let freelancer_share = amount * policy.freelancer_bps as i128 / BPS_DENOMINATOR as i128;
+        let platform_share = amount * policy.platform_bps as i128 / BPS_DENOMINATOR as i128;
+        let client_share = amount - freelancer_share - platform_share;
+
+        let mut paid = YieldShares {
+            client: Self::claim_yield(env, job, strategy, client_share, &job.client),
+            freelancer: Self::claim_yield(env, job, strategy, freelancer_share, &job.freelancer),
+            platform: 0,
+        };
+        if platform_share > 0 {
+            let treasury = Self::get_treasury(env.clone()).ok_or(Error::TreasuryNotSet)?;
+            paid.platform = Self::claim_yield(env, job, strategy, platform_share, &treasury);
+        }
+
+        // The shortfall stays in the strategy position, set aside for this job
+        let total = paid.client + paid.freelancer + paid.platform;
+        if total < amount {
+            let unclaimed = storage::read_unclaimed_yield(env, &job.job_id);
+            storage::write_unclaimed_yield(env, &job.job_id, unclaimed + amount - total);
+            Self::adjust_reserved_yield(env, strategy, &job.asset_address, amount - total);
+        }
+        if total == 0 {
+            return Ok(0);
+        }
+
+        let mut shares = storage::read_yield_shares(env, &job.job_id);
+        shares.client += paid.client;
+        shares.freelancer += paid.freelancer;
+        shares.platform += paid.platform;
+        storage::write_yield_shares(env, &job.job_id, &shares);
+
+        events::YieldDistributed {
+            job_id: job.job_id.clone(),
+            client_amount: paid.client,
+            freelancer_amount: paid.freelancer,
+            platform_amount: paid.platform,
+        }
+        .publish(env);
+
+        Ok(total)
+    }
+
+    /// Change the yield set aside for particular jobs in a strategy position, which
+    /// other jobs' withdrawals must not share out
+    fn adjust_reserved_yield(env: &Env, strategy: &Address, asset: &Address, delta: i128) {
+        let reserved = storage::read_strategy_unclaimed_yield(env, strategy, asset);
+        storage::write_strategy_unclaimed_yield(env, strategy, asset, reserved + delta);
+    }
+
+    fn claim_yield(env: &Env, job: &Job, strategy: &Address, amount: i128, recipient: &Address) -> i128 {
+        if amount <= 0 {
+            return 0;
+        }
+        YieldStrategyClient::new(env, strategy).strategy_claim_yield(
+            &env.current_contract_address(),
+            &job.asset_address,
+            &amount,
+            recipient,
+        )
+    }
+}
@@ -1,12 +1,28 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, token, Address, Env, Symbol};
+
+/// Error codes are stable: never renumber, only append.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    InvalidAmount = 2,
+    NotInitialized = 3,
+    PairNotSupported = 4,
+    SlippageExceeded = 5,
+    InsufficientLiquidity = 6,
+}
+
+/// Fixed-point scale of exchange rates: a rate of `RATE_SCALE` converts one to one
+pub const RATE_SCALE: i128 = 10_000_000;
 
 #[contracttype]
 #[derive(Clone)]
 pub struct ExchangeRate {
     pub from_token: Address,
     pub to_token: Address,
-    pub rate: i128, // multiplier (83 for USD->INR)
+    pub rate: i128, // units of to_token per unit of from_token, times RATE_SCALE (83 * RATE_SCALE for USD->INR)
 }
 
 #[contract]
@@ -14,69 +30,141 @@ pub struct LiquidityRouter;
 
 #[contractimpl]
 impl LiquidityRouter {
-    /// Initialize router with exchange rates
-    pub fn initialize(env: Env, yield_harvester: Address) {
+    /// Initialize router with the admin that sets rates and liquidity, and the
+    /// EscrowCore allowed to convert through it
+    pub fn initialize(env: Env, admin: Address, escrow: Address) -> Result<(), Error> {
         if env.storage().instance().has(&Symbol::new(&env, "init")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
-        env.storage().instance().set(&Symbol::new(&env, "yield_harvester"), &yield_harvester);
+        env.storage().instance().set(&Symbol::new(&env, "admin"), &admin);
+        env.storage().instance().set(&Symbol::new(&env, "escrow"), &escrow);
         env.storage().instance().set(&Symbol::new(&env, "init"), &());
+
+        Ok(())
+    }
+
+    /// Admin: quote `rate / RATE_SCALE` units of `to_token` for each unit of `from_token`
+    pub fn set_exchange_rate(env: Env, from_token: Address, to_token: Address, rate: i128) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+        if rate <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let key = (Symbol::new(&env, "rate"), from_token.clone(), to_token.clone());
+        env.storage().instance().set(&key, &ExchangeRate { from_token, to_token, rate });
+
+        Ok(())
     }
 
-    /// Convert and send tokens with mock exchange rates
+    /// Take `amount` of `from_asset` from the escrow, convert it into `to_asset` at the
+    /// quoted rate and send it to `recipient`. Fails, taking nothing, unless at least
+    /// `min_amount_out` can be sent.
     pub fn convert_and_send(
         env: Env,
         from_asset: Address,
         to_asset: Address,
         amount: i128,
         recipient: Address,
-        max_slippage: i128,
-    ) -> i128 {
-        // Mock exchange rates for demo
-        let usdc_address = Address::from_string(&soroban_sdk::String::from_str(&env, "USDC_TOKEN_ADDRESS"));
-        let inr_address = Address::from_string(&soroban_sdk::String::from_str(&env, "INR_TOKEN_ADDRESS"));
-
-        let converted_amount = if from_asset == usdc_address && to_asset == inr_address {
-            // USD to INR: 1 USD = 83 INR
-            amount * 83
-        } else if from_asset == inr_address && to_asset == usdc_address {
-            // INR to USD: 1 INR = 1/83 USD
-            amount / 83
-        } else {
-            // Same token or unsupported pair
-            amount
-        };
-
-        // For demo, we'll just return the converted amount
-        // In production, this would handle actual token transfers
-        converted_amount
+        min_amount_out: i128,
+    ) -> Result<i128, Error> {
+        let escrow: Address = env.storage().instance()
+            .get(&Symbol::new(&env, "escrow"))
+            .ok_or(Error::NotInitialized)?;
+        escrow.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let rate = Self::get_exchange_rate(env.clone(), from_asset.clone(), to_asset.clone())?;
+        let converted_amount = amount
+            .checked_mul(rate)
+            .ok_or(Error::InvalidAmount)?
+            / RATE_SCALE;
+        if converted_amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        if converted_amount < min_amount_out {
+            return Err(Error::SlippageExceeded);
+        }
+        let to_reserve = Self::get_liquidity(env.clone(), to_asset.clone());
+        if to_reserve < converted_amount {
+            return Err(Error::InsufficientLiquidity);
+        }
+
+        token::TokenClient::new(&env, &from_asset).transfer(&escrow, env.current_contract_address(), &amount);
+        let from_reserve = Self::get_liquidity(env.clone(), from_asset.clone());
+        Self::write_liquidity(&env, &from_asset, from_reserve + amount);
+        Self::write_liquidity(&env, &to_asset, to_reserve - converted_amount);
+
+        token::TokenClient::new(&env, &to_asset).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &converted_amount,
+        );
+
+        Ok(converted_amount)
     }
 
-    /// Get exchange rate between two tokens
-    pub fn get_exchange_rate(env: Env, from_asset: Address, to_asset: Address) -> i128 {
-        let usdc_address = Address::from_string(&soroban_sdk::String::from_str(&env, "USDC_TOKEN_ADDRESS"));
-        let inr_address = Address::from_string(&soroban_sdk::String::from_str(&env, "INR_TOKEN_ADDRESS"));
-
-        if from_asset == usdc_address && to_asset == inr_address {
-            83 // USD to INR
-        } else if from_asset == inr_address && to_asset == usdc_address {
-            1 // INR to USD (1/83, but we'll handle division in conversion)
-        } else {
-            1 // Default rate
+    /// Get exchange rate between two tokens, scaled by `RATE_SCALE`
+    pub fn get_exchange_rate(env: Env, from_asset: Address, to_asset: Address) -> Result<i128, Error> {
+        if from_asset == to_asset {
+            return Ok(RATE_SCALE);
         }
+
+        let key = (Symbol::new(&env, "rate"), from_asset, to_asset);
+        env.storage().instance()
+            .get::<_, ExchangeRate>(&key)
+            .map(|quote| quote.rate)
+            .ok_or(Error::PairNotSupported)
+    }
+
+    /// Provide `amount` of `token` as liquidity for conversions into it
+    pub fn add_liquidity(env: Env, provider: Address, token: Address, amount: i128) -> Result<(), Error> {
+        provider.require_auth();
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        token::TokenClient::new(&env, &token).transfer(&provider, env.current_contract_address(), &amount);
+        let reserve = Self::get_liquidity(env.clone(), token.clone());
+        Self::write_liquidity(&env, &token, reserve + amount);
+
+        Ok(())
     }
 
-    /// Add liquidity (mock implementation)
-    pub fn add_liquidity(env: Env, token_a: Address, token_b: Address, amount_a: i128, amount_b: i128) {
-        // Mock implementation - just store liquidity info
-        let liquidity_key = Symbol::new(&env, "liquidity");
-        env.storage().instance().set(&liquidity_key, &(amount_a + amount_b));
+    /// Admin: take `amount` of `token` liquidity out to `recipient`
+    pub fn remove_liquidity(env: Env, token: Address, amount: i128, recipient: Address) -> Result<(), Error> {
+        Self::read_admin(&env)?.require_auth();
+
+        let reserve = Self::get_liquidity(env.clone(), token.clone());
+        if amount <= 0 || amount > reserve {
+            return Err(Error::InvalidAmount);
+        }
+        Self::write_liquidity(&env, &token, reserve - amount);
+        token::TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &recipient, &amount);
+
+        Ok(())
     }
 
-    /// Get total liquidity (mock implementation)
-    pub fn get_total_liquidity(env: Env) -> i128 {
-        let liquidity_key = Symbol::new(&env, "liquidity");
+    /// Get the liquidity held for a token
+    pub fn get_liquidity(env: Env, token: Address) -> i128 {
+        let liquidity_key = (Symbol::new(&env, "liquidity"), token);
         env.storage().instance().get(&liquidity_key).unwrap_or(0)
     }
 }
+
+impl LiquidityRouter {
+    fn read_admin(env: &Env) -> Result<Address, Error> {
+        env.storage().instance()
+            .get(&Symbol::new(env, "admin"))
+            .ok_or(Error::NotInitialized)
+    }
+
+    fn write_liquidity(env: &Env, token: &Address, amount: i128) {
+        let liquidity_key = (Symbol::new(env, "liquidity"), token.clone());
+        env.storage().instance().set(&liquidity_key, &amount);
+    }
+}
+
+mod test;
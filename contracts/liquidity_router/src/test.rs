@@ -0,0 +1,112 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{token, Address, Env};
+
+struct Setup<'a> {
+    router: LiquidityRouterClient<'a>,
+    escrow: Address,
+    usdc: Address,
+    inr: Address,
+}
+
+fn setup(env: &Env) -> Setup<'_> {
+    env.mock_all_auths();
+    let router = LiquidityRouterClient::new(env, &env.register(LiquidityRouter, ()));
+    let escrow = Address::generate(env);
+    router.initialize(&Address::generate(env), &escrow);
+
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    let inr = env.register_stellar_asset_contract_v2(Address::generate(env)).address();
+    router.set_exchange_rate(&usdc, &inr, &(83 * RATE_SCALE));
+
+    let provider = Address::generate(env);
+    token::StellarAssetClient::new(env, &inr).mint(&provider, &100_000);
+    router.add_liquidity(&provider, &inr, &100_000);
+
+    Setup { router, escrow, usdc, inr }
+}
+
+#[test]
+fn convert_and_send_delivers_converted_tokens() {
+    let env = Env::default();
+    let s = setup(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.usdc).mint(&s.escrow, &100);
+
+    assert_eq!(s.router.convert_and_send(&s.usdc, &s.inr, &100, &recipient, &8_300), 8_300);
+    assert_eq!(token::Client::new(&env, &s.inr).balance(&recipient), 8_300);
+    assert_eq!(token::Client::new(&env, &s.usdc).balance(&s.escrow), 0);
+    assert_eq!(s.router.get_liquidity(&s.inr), 91_700);
+    assert_eq!(s.router.get_liquidity(&s.usdc), 100);
+}
+
+#[test]
+fn convert_and_send_takes_nothing_below_the_minimum() {
+    let env = Env::default();
+    let s = setup(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.usdc).mint(&s.escrow, &100);
+
+    assert_eq!(
+        s.router.try_convert_and_send(&s.usdc, &s.inr, &100, &recipient, &8_301),
+        Err(Ok(Error::SlippageExceeded))
+    );
+    assert_eq!(token::Client::new(&env, &s.usdc).balance(&s.escrow), 100);
+    assert_eq!(s.router.get_liquidity(&s.inr), 100_000);
+}
+
+#[test]
+fn convert_and_send_rejects_unquoted_pairs_and_missing_liquidity() {
+    let env = Env::default();
+    let s = setup(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.inr).mint(&s.escrow, &830);
+
+    assert_eq!(s.router.try_get_exchange_rate(&s.inr, &s.usdc), Err(Ok(Error::PairNotSupported)));
+    assert_eq!(
+        s.router.try_convert_and_send(&s.inr, &s.usdc, &830, &recipient, &0),
+        Err(Ok(Error::PairNotSupported))
+    );
+
+    // 1/83 USDC per INR needs a fractional rate
+    s.router.set_exchange_rate(&s.inr, &s.usdc, &(RATE_SCALE / 83));
+    assert_eq!(
+        s.router.try_convert_and_send(&s.inr, &s.usdc, &830, &recipient, &0),
+        Err(Ok(Error::InsufficientLiquidity))
+    );
+    // Nothing was taken from the escrow
+    assert_eq!(token::Client::new(&env, &s.inr).balance(&s.escrow), 830);
+    assert_eq!(s.router.get_liquidity(&s.inr), 100_000);
+}
+
+#[test]
+fn convert_and_send_rounds_fractional_rates_down() {
+    let env = Env::default();
+    let s = setup(&env);
+    let recipient = Address::generate(&env);
+    let provider = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.usdc).mint(&provider, &1_000);
+    s.router.add_liquidity(&provider, &s.usdc, &1_000);
+    token::StellarAssetClient::new(&env, &s.inr).mint(&s.escrow, &8_300);
+
+    s.router.set_exchange_rate(&s.inr, &s.usdc, &(RATE_SCALE / 83));
+    assert_eq!(s.router.get_exchange_rate(&s.usdc, &s.usdc), RATE_SCALE);
+    // 8_300 * 120_481 / 10_000_000 = 99.99...
+    assert_eq!(s.router.convert_and_send(&s.inr, &s.usdc, &8_300, &recipient, &99), 99);
+    assert_eq!(token::Client::new(&env, &s.usdc).balance(&recipient), 99);
+    assert_eq!(s.router.get_liquidity(&s.usdc), 901);
+}
+
+#[test]
+fn convert_and_send_is_restricted_to_the_escrow() {
+    let env = Env::default();
+    let s = setup(&env);
+    let recipient = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &s.usdc).mint(&s.escrow, &100);
+
+    s.router.convert_and_send(&s.usdc, &s.inr, &100, &recipient, &0);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, s.escrow);
+}
@@ -1,5 +1,17 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol, token};
+use soroban_sdk::{contract, contracterror, contractevent, contractimpl, contracttype, Address, Env, Symbol, token};
+
+/// Error codes are stable: never renumber, only append.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    InsufficientDeposit = 1,
+    PositionNotFound = 2,
+    InsufficientOusgBalance = 3,
+    InsufficientPrincipal = 4,
+    OusgNotConfigured = 5,
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -12,6 +24,49 @@ pub struct YieldPosition {
     pub total_yield_earned: i128,     // Cumulative yield
 }
 
+// Events keep the topic and positional data layout of the original `publish` calls
+#[contractevent(topics = ["deposit"], data_format = "vec")]
+#[derive(Clone)]
+pub struct Deposited {
+    pub owner: Address,
+    pub usdc_amount: i128,
+    pub ousg_amount: i128,
+}
+
+#[contractevent(topics = ["yield_harvested"], data_format = "vec")]
+#[derive(Clone)]
+pub struct YieldHarvested {
+    pub owner: Address,
+    pub yield_amount: i128,
+    pub total_yield_earned: i128,
+}
+
+#[contractevent(topics = ["withdraw"], data_format = "vec")]
+#[derive(Clone)]
+pub struct Withdrawn {
+    pub owner: Address,
+    pub ousg_amount: i128,
+    pub usdc_amount: i128,
+}
+
+#[contractevent(topics = ["principal_withdrawn"], data_format = "vec")]
+#[derive(Clone)]
+pub struct PrincipalWithdrawn {
+    pub owner: Address,
+    pub usdc_principal_amount: i128,
+    pub usdc_amount: i128,
+    pub recipient: Address,
+}
+
+#[contractevent(topics = ["yield_claimed"], data_format = "vec")]
+#[derive(Clone)]
+pub struct YieldClaimed {
+    pub owner: Address,
+    pub ousg_amount: i128,
+    pub usdc_amount: i128,
+    pub recipient: Address,
+}
+
 #[contract]
 pub struct RwaYieldHarvester;
 
@@ -29,13 +84,13 @@ impl RwaYieldHarvester {
         
         // Transfer USDC from user to this contract
         let usdc_client = token::TokenClient::new(&env, &usdc_token);
-        usdc_client.transfer(&from, &env.current_contract_address(), &usdc_amount);
+        usdc_client.transfer(&from, env.current_contract_address(), &usdc_amount);
         
         Self::process_deposit(env, from, usdc_amount, usdc_token, ousg_token)
     }
     
     /// Deposit from contract (for EscrowCore integration)
-    /// Requires USDC to already be transferred to this contract
+    /// Pulls the USDC from the calling contract and credits it to `owner`
     pub fn deposit_from_contract(
         env: Env,
         from_contract: Address,
@@ -43,16 +98,19 @@ impl RwaYieldHarvester {
         usdc_amount: i128,
         usdc_token: Address,
         ousg_token: Address,
-    ) -> i128 {
-        // Verify USDC was already transferred to this contract
-        let usdc_client = token::TokenClient::new(&env, &usdc_token);
-        let balance = usdc_client.balance(&env.current_contract_address());
-        if balance < usdc_amount {
-            panic!("Insufficient USDC transferred to contract");
+    ) -> Result<i128, Error> {
+        from_contract.require_auth();
+
+        if usdc_amount <= 0 {
+            return Err(Error::InsufficientDeposit);
         }
+
+        // Transfer USDC from the calling contract to this contract
+        let usdc_client = token::TokenClient::new(&env, &usdc_token);
+        usdc_client.transfer(&from_contract, env.current_contract_address(), &usdc_amount);
         
         // Process deposit for the owner (client), not the calling contract
-        Self::process_deposit(env, owner, usdc_amount, usdc_token, ousg_token)
+        Ok(Self::process_deposit(env, owner, usdc_amount, usdc_token, ousg_token))
     }
     
     /// Internal: Process deposit and create/update position
@@ -97,10 +155,12 @@ impl RwaYieldHarvester {
         env.storage().persistent().set(&position_key, &position);
         
         // Emit event
-        env.events().publish(
-            (Symbol::new(&env, "deposit"),),
-            (owner, usdc_amount, ousg_amount)
-        );
+        Deposited {
+            owner,
+            usdc_amount,
+            ousg_amount,
+        }
+        .publish(&env);
         
         ousg_amount
     }
@@ -109,9 +169,10 @@ impl RwaYieldHarvester {
     pub fn calculate_yield(
         env: Env,
         owner: Address,
-    ) -> i128 {
+    ) -> Result<i128, Error> {
         let position_key = (Symbol::new(&env, "position"), owner);
-        let position: YieldPosition = env.storage().persistent().get(&position_key).unwrap();
+        let position: YieldPosition = env.storage().persistent().get(&position_key)
+            .ok_or(Error::PositionNotFound)?;
         
         // Time since last harvest (in seconds)
         let duration = env.ledger().timestamp() - position.last_harvest_at;
@@ -125,21 +186,22 @@ impl RwaYieldHarvester {
         let yield_earned = (position.ousg_balance * apy_bps * duration as i128) 
                           / (seconds_per_year as i128 * 10000);
         
-        yield_earned
+        Ok(yield_earned)
     }
     
     /// Harvest (compound) accrued yield
     pub fn harvest_yield(
         env: Env,
         owner: Address,
-    ) -> i128 {
+    ) -> Result<i128, Error> {
         owner.require_auth();
         
         let position_key = (Symbol::new(&env, "position"), owner.clone());
-        let mut position: YieldPosition = env.storage().persistent().get(&position_key).unwrap();
+        let mut position: YieldPosition = env.storage().persistent().get(&position_key)
+            .ok_or(Error::PositionNotFound)?;
         
         // Calculate yield since last harvest
-        let yield_amount = Self::calculate_yield(env.clone(), owner.clone());
+        let yield_amount = Self::calculate_yield(env.clone(), owner.clone())?;
         
         // Compound yield back into OUSG balance
         position.ousg_balance += yield_amount;
@@ -149,12 +211,14 @@ impl RwaYieldHarvester {
         env.storage().persistent().set(&position_key, &position);
         
         // Emit event
-        env.events().publish(
-            (Symbol::new(&env, "yield_harvested"),),
-            (owner, yield_amount, position.total_yield_earned)
-        );
+        YieldHarvested {
+            owner,
+            yield_amount,
+            total_yield_earned: position.total_yield_earned,
+        }
+        .publish(&env);
         
-        yield_amount
+        Ok(yield_amount)
     }
     
     /// Withdraw OUSG back to USDC
@@ -164,19 +228,20 @@ impl RwaYieldHarvester {
         ousg_amount: i128,
         ousg_token: Address,
         usdc_token: Address,
-    ) -> i128 {
+    ) -> Result<i128, Error> {
         owner.require_auth();
         
         let position_key = (Symbol::new(&env, "position"), owner.clone());
-        let mut position: YieldPosition = env.storage().persistent().get(&position_key).unwrap();
+        let mut position: YieldPosition = env.storage().persistent().get(&position_key)
+            .ok_or(Error::PositionNotFound)?;
         
         // Ensure sufficient balance
         if position.ousg_balance < ousg_amount {
-            panic!("Insufficient OUSG balance");
+            return Err(Error::InsufficientOusgBalance);
         }
         
         // Harvest any pending yield first
-        let pending_yield = Self::calculate_yield(env.clone(), owner.clone());
+        let pending_yield = Self::calculate_yield(env.clone(), owner.clone())?;
         if pending_yield > 0 {
             position.ousg_balance += pending_yield;
             position.total_yield_earned += pending_yield;
@@ -200,12 +265,14 @@ impl RwaYieldHarvester {
         env.storage().persistent().set(&position_key, &position);
         
         // Emit event
-        env.events().publish(
-            (Symbol::new(&env, "withdraw"),),
-            (owner, ousg_amount, usdc_amount)
-        );
+        Withdrawn {
+            owner,
+            ousg_amount,
+            usdc_amount,
+        }
+        .publish(&env);
         
-        usdc_amount
+        Ok(usdc_amount)
     }
     
     /// Withdraw only principal amount (in USDC terms) without harvesting yield
@@ -216,13 +283,13 @@ impl RwaYieldHarvester {
         usdc_principal_amount: i128,
         ousg_token: Address,
         usdc_token: Address,
-    ) -> i128 {
+    ) -> Result<i128, Error> {
         owner.require_auth();
-        Self::withdraw_principal_internal(env, owner, usdc_principal_amount, owner.clone(), ousg_token, usdc_token)
+        Self::withdraw_principal_internal(env, owner.clone(), usdc_principal_amount, owner, ousg_token, usdc_token)
     }
     
     /// Withdraw principal on behalf of owner (for EscrowCore integration)
-    /// Transfers USDC to recipient (EscrowCore) instead of owner
+    /// Transfers USDC to recipient (EscrowCore) instead of owner; the owner must authorize it
     pub fn withdraw_principal_for_owner(
         env: Env,
         owner: Address,
@@ -230,9 +297,8 @@ impl RwaYieldHarvester {
         recipient: Address, // Where to send USDC (usually EscrowCore)
         ousg_token: Address,
         usdc_token: Address,
-    ) -> i128 {
-        // In production, verify calling contract is EscrowCore
-        // For now, allow any contract call (can add whitelist later)
+    ) -> Result<i128, Error> {
+        owner.require_auth();
         Self::withdraw_principal_internal(env, owner, usdc_principal_amount, recipient, ousg_token, usdc_token)
     }
     
@@ -244,14 +310,15 @@ impl RwaYieldHarvester {
         recipient: Address,
         ousg_token: Address,
         usdc_token: Address,
-    ) -> i128 {
+    ) -> Result<i128, Error> {
         
         let position_key = (Symbol::new(&env, "position"), owner.clone());
-        let mut position: YieldPosition = env.storage().persistent().get(&position_key).unwrap();
+        let mut position: YieldPosition = env.storage().persistent().get(&position_key)
+            .ok_or(Error::PositionNotFound)?;
         
         // Ensure we have enough principal to withdraw
         if position.principal < usdc_principal_amount {
-            panic!("Insufficient principal balance");
+            return Err(Error::InsufficientPrincipal);
         }
         
         // Calculate how much OUSG corresponds to this principal amount
@@ -261,7 +328,7 @@ impl RwaYieldHarvester {
         
         // Ensure sufficient OUSG balance
         if position.ousg_balance < ousg_to_withdraw {
-            panic!("Insufficient OUSG balance");
+            return Err(Error::InsufficientOusgBalance);
         }
         
         // Redeem OUSG for USDC (1:1 for mock)
@@ -282,21 +349,25 @@ impl RwaYieldHarvester {
         env.storage().persistent().set(&position_key, &position);
         
         // Emit event
-        env.events().publish(
-            (Symbol::new(&env, "principal_withdrawn"),),
-            (owner, usdc_principal_amount, usdc_amount, recipient)
-        );
+        PrincipalWithdrawn {
+            owner,
+            usdc_principal_amount,
+            usdc_amount,
+            recipient,
+        }
+        .publish(&env);
         
-        usdc_amount
+        Ok(usdc_amount)
     }
     
     /// Get position details
     pub fn get_position(
         env: Env,
         owner: Address,
-    ) -> YieldPosition {
+    ) -> Result<YieldPosition, Error> {
         let position_key = (Symbol::new(&env, "position"), owner);
-        env.storage().persistent().get(&position_key).unwrap()
+        env.storage().persistent().get(&position_key)
+            .ok_or(Error::PositionNotFound)
     }
     
     /// Internal: Swap USDC for OUSG
@@ -341,6 +412,7 @@ impl RwaYieldHarvester {
     }
     
     /// Admin: Set OUSG reserves (for testing)
+    /// Also records the OUSG token used by the YieldStrategy interface.
     pub fn set_ousg_reserve(
         env: Env,
         admin: Address,
@@ -351,6 +423,122 @@ impl RwaYieldHarvester {
         
         let key = (Symbol::new(&env, "ousg_reserve"),);
         env.storage().persistent().set(&key, &amount);
+        env.storage().persistent().set(&(Symbol::new(&env, "ousg_token"),), &ousg_token);
+    }
+
+    // YieldStrategy interface
+    // `token` is the USDC token; positions are the same OUSG positions used above,
+    // with accrued yield compounded before every change.
+
+    /// Take `amount` USDC from `owner` and add it to the owner's position
+    pub fn strategy_deposit(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), Error> {
+        owner.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InsufficientDeposit);
+        }
+        token::TokenClient::new(&env, &token).transfer(&owner, env.current_contract_address(), &amount);
+
+        let ousg_token = Self::get_ousg_token(&env)?;
+        if env.storage().persistent().has(&(Symbol::new(&env, "position"), owner.clone())) {
+            Self::compound(&env, &owner)?;
+        }
+        Self::process_deposit(env, owner, amount, token, ousg_token);
+
+        Ok(())
+    }
+
+    /// Send `amount` of the owner's principal (in USDC) to `recipient`
+    pub fn strategy_withdraw_principal(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+
+        let ousg_token = Self::get_ousg_token(&env)?;
+        Self::compound(&env, &owner)?;
+        Self::withdraw_principal_internal(env, owner, amount, recipient, ousg_token, token)
+    }
+
+    /// Redeem up to `amount` of the owner's accrued yield and send the USDC to `recipient`
+    pub fn strategy_claim_yield(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+
+        let ousg_token = Self::get_ousg_token(&env)?;
+        let mut position = Self::compound(&env, &owner)?;
+
+        let accrued = position.ousg_balance - position.principal;
+        let ousg_amount = amount.min(accrued).max(0);
+        if ousg_amount == 0 {
+            return Ok(0);
+        }
+
+        let usdc_amount = Self::redeem_ousg_to_usdc(env.clone(), ousg_token, token.clone(), ousg_amount);
+        token::TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &recipient, &usdc_amount);
+
+        position.ousg_balance -= ousg_amount;
+        env.storage().persistent().set(&(Symbol::new(&env, "position"), owner.clone()), &position);
+
+        YieldClaimed {
+            owner,
+            ousg_amount,
+            usdc_amount,
+            recipient,
+        }
+        .publish(&env);
+
+        Ok(usdc_amount)
+    }
+
+    /// Principal plus accrued yield (in USDC at 1:1) currently held for `owner`
+    pub fn strategy_preview_balance(env: Env, owner: Address, _token: Address) -> i128 {
+        let position_key = (Symbol::new(&env, "position"), owner.clone());
+        match env.storage().persistent().get::<_, YieldPosition>(&position_key) {
+            Some(position) => position.ousg_balance + Self::calculate_yield(env, owner).unwrap_or(0),
+            None => 0,
+        }
+    }
+}
+
+impl RwaYieldHarvester {
+    /// Compound pending yield into the owner's position, if it exists
+    fn compound(env: &Env, owner: &Address) -> Result<YieldPosition, Error> {
+        let position_key = (Symbol::new(env, "position"), owner.clone());
+        let mut position: YieldPosition = match env.storage().persistent().get(&position_key) {
+            Some(position) => position,
+            None => return Err(Error::PositionNotFound),
+        };
+
+        let pending_yield = Self::calculate_yield(env.clone(), owner.clone())?;
+        if pending_yield > 0 {
+            position.ousg_balance += pending_yield;
+            position.total_yield_earned += pending_yield;
+        }
+        position.last_harvest_at = env.ledger().timestamp();
+        env.storage().persistent().set(&position_key, &position);
+
+        Ok(position)
+    }
+
+    fn get_ousg_token(env: &Env) -> Result<Address, Error> {
+        env.storage()
+            .persistent()
+            .get(&(Symbol::new(env, "ousg_token"),))
+            .ok_or(Error::OusgNotConfigured)
     }
 }
 
@@ -358,3 +546,5 @@ impl RwaYieldHarvester {
 // 1. Use OpenEden's Soroban contract (when available)
 // 2. Or bridge OUSG from Ethereum using Stellar anchors
 // 3. Implement real redemption queue and settlement
+
+mod test;
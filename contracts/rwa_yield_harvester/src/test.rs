@@ -0,0 +1,82 @@
+#![cfg(test)]
+use super::*;
+use soroban_sdk::testutils::{Address as _, MockAuth, MockAuthInvoke};
+use soroban_sdk::IntoVal;
+use soroban_sdk::{token, Address, Env};
+
+#[test]
+fn strategy_deposit_without_tokens_is_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let harvester = RwaYieldHarvesterClient::new(&env, &env.register(RwaYieldHarvester, ()));
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let ousg = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    harvester.set_ousg_reserve(&Address::generate(&env), &ousg, &0);
+
+    let victim = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&victim, &1_000);
+    harvester.strategy_deposit(&victim, &usdc, &1_000);
+    assert_eq!(harvester.get_position(&victim).principal, 1_000);
+
+    // The contract holds the victim's USDC, but the attacker sent nothing
+    assert!(harvester.try_strategy_deposit(&attacker, &usdc, &1_000).is_err());
+    assert!(harvester.try_get_position(&attacker).is_err());
+    assert_eq!(token::Client::new(&env, &usdc).balance(&harvester.address), 1_000);
+}
+
+#[test]
+fn deposit_from_contract_pulls_the_usdc_it_credits() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let harvester = RwaYieldHarvesterClient::new(&env, &env.register(RwaYieldHarvester, ()));
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let ousg = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let usdc_client = token::Client::new(&env, &usdc);
+
+    let escrow = Address::generate(&env);
+    let client = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    token::StellarAssetClient::new(&env, &usdc).mint(&escrow, &1_000);
+
+    harvester.deposit_from_contract(&escrow, &client, &1_000, &usdc, &ousg);
+    assert_eq!(usdc_client.balance(&escrow), 0);
+    assert_eq!(usdc_client.balance(&harvester.address), 1_000);
+    assert_eq!(harvester.get_position(&client).principal, 1_000);
+
+    // USDC already held by the contract cannot be credited a second time
+    assert!(harvester.try_deposit_from_contract(&attacker, &attacker, &1_000, &usdc, &ousg).is_err());
+    assert!(harvester.try_get_position(&attacker).is_err());
+}
+
+#[test]
+fn withdraw_principal_for_owner_needs_the_owner() {
+    let env = Env::default();
+    let harvester = RwaYieldHarvesterClient::new(&env, &env.register(RwaYieldHarvester, ()));
+    let usdc = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+    let ousg = env.register_stellar_asset_contract_v2(Address::generate(&env)).address();
+
+    let owner = Address::generate(&env);
+    let attacker = Address::generate(&env);
+    env.mock_all_auths();
+    token::StellarAssetClient::new(&env, &usdc).mint(&owner, &1_000);
+    harvester.deposit(&owner, &1_000, &usdc, &ousg);
+
+    // Only the attacker signs, so the owner's principal stays put
+    env.mock_auths(&[MockAuth {
+        address: &attacker,
+        invoke: &MockAuthInvoke {
+            contract: &harvester.address,
+            fn_name: "withdraw_principal_for_owner",
+            args: (&owner, 1_000_i128, &attacker, &ousg, &usdc).into_val(&env),
+            sub_invokes: &[],
+        },
+    }]);
+    assert!(harvester
+        .try_withdraw_principal_for_owner(&owner, &1_000, &attacker, &ousg, &usdc)
+        .is_err());
+
+    env.mock_all_auths();
+    assert_eq!(harvester.withdraw_principal_for_owner(&owner, &1_000, &owner, &ousg, &usdc), 1_000);
+    assert_eq!(harvester.get_position(&owner).principal, 0);
+}
@@ -1,5 +1,14 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, String, Symbol};
+
+/// Error codes are stable: never renumber, only append.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    InsufficientBalance = 2,
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -14,9 +23,9 @@ pub struct SimpleToken;
 
 #[contractimpl]
 impl SimpleToken {
-    pub fn initialize(env: Env, name: String, symbol: String, decimals: u32) {
+    pub fn initialize(env: Env, name: String, symbol: String, decimals: u32) -> Result<(), Error> {
         if env.storage().instance().has(&Symbol::new(&env, "init")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         let token = Token {
@@ -27,6 +36,8 @@ impl SimpleToken {
 
         env.storage().instance().set(&Symbol::new(&env, "token"), &token);
         env.storage().instance().set(&Symbol::new(&env, "init"), &());
+
+        Ok(())
     }
 
     pub fn mint(env: Env, _to: Address, amount: i128) {
@@ -41,17 +52,19 @@ impl SimpleToken {
         env.storage().instance().get(&balance_key).unwrap_or(0)
     }
 
-    pub fn transfer(env: Env, from: Address, to: Address, amount: i128) {
+    pub fn transfer(env: Env, _from: Address, _to: Address, amount: i128) -> Result<(), Error> {
         // For simplicity, just check if there's enough balance and transfer
         let balance_key = Symbol::new(&env, "balance");
         let current_balance: i128 = env.storage().instance().get(&balance_key).unwrap_or(0);
         
         if current_balance < amount {
-            panic!("insufficient balance");
+            return Err(Error::InsufficientBalance);
         }
         
         // For demo purposes, just reduce the balance
         let new_balance = current_balance - amount;
         env.storage().instance().set(&balance_key, &new_balance);
+
+        Ok(())
     }
 }
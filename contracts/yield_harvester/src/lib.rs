@@ -1,5 +1,17 @@
 #![no_std]
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, String, Symbol, Map, token, IntoVal};
+use soroban_sdk::{contract, contracterror, contractimpl, contracttype, Address, Env, Symbol, token};
+
+/// Error codes are stable: never renumber, only append.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    AlreadyInitialized = 1,
+    NoPosition = 2,
+    InsufficientPrincipal = 3,
+    InvalidAmount = 4,
+    TokenMismatch = 5,
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -10,27 +22,45 @@ pub struct Position {
     pub token_address: Address,
 }
 
+/// Position held through the YieldStrategy interface, one per owner and token
+#[contracttype]
+#[derive(Clone)]
+pub struct StrategyPosition {
+    pub principal: i128,
+    pub yield_accrued: i128,
+    pub updated_at: u64,
+}
+
+const SECONDS_PER_YEAR: u64 = 31_536_000;
+
 #[contract]
 pub struct YieldHarvester;
 
 #[contractimpl]
 impl YieldHarvester {
     /// Initialize yield harvester
-    pub fn initialize(env: Env, base_rate: u32, bonus_rate: u32, lock_period: u64) {
+    pub fn initialize(env: Env, base_rate: u32, bonus_rate: u32, lock_period: u64) -> Result<(), Error> {
         if env.storage().instance().has(&Symbol::new(&env, "init")) {
-            panic!("already initialized");
+            return Err(Error::AlreadyInitialized);
         }
 
         env.storage().instance().set(&Symbol::new(&env, "base_rate"), &base_rate);
         env.storage().instance().set(&Symbol::new(&env, "bonus_rate"), &bonus_rate);
         env.storage().instance().set(&Symbol::new(&env, "lock_period"), &lock_period);
         env.storage().instance().set(&Symbol::new(&env, "init"), &());
+
+        Ok(())
     }
 
     /// Deposit funds for yield generation (legacy - with transfer)
-    pub fn deposit(env: Env, owner: Address, amount: i128, token_address: Address) {
-        let position_key = Symbol::new(&env, "position");
-        
+    pub fn deposit(env: Env, owner: Address, amount: i128, token_address: Address) -> Result<(), Error> {
+        owner.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let position_key = Self::position_key(&env, &owner);
         let mut position: Position = env.storage().instance()
             .get(&position_key)
             .unwrap_or(Position {
@@ -40,127 +70,58 @@ impl YieldHarvester {
                 token_address: token_address.clone(),
             });
 
-        position.principal += amount;
-        position.token_address = token_address.clone();
-        env.storage().instance().set(&position_key, &position);
+        // A position holds a single token
+        if position.principal > 0 && position.token_address != token_address {
+            return Err(Error::TokenMismatch);
+        }
 
-        // Track total deposits
-        let total_key = Symbol::new(&env, "total_deposits");
-        let total: i128 = env.storage().instance().get(&total_key).unwrap_or(0);
-        env.storage().instance().set(&total_key, &(total + amount));
-        
         // Transfer tokens from depositor to this contract
         let token_client = token::TokenClient::new(&env, &token_address);
-        token_client.transfer(&owner, &env.current_contract_address(), &amount);
-    }
-
-    /// Track deposit (tokens already transferred to this contract)
-    pub fn track_deposit(env: Env, owner: Address, amount: i128, token_address: Address) {
-        let position_key = Symbol::new(&env, "position");
-        
-        let mut position: Position = env.storage().instance()
-            .get(&position_key)
-            .unwrap_or(Position {
-                principal: 0,
-                yield_earned: 0,
-                created_at: env.ledger().timestamp(),
-                token_address: token_address.clone(),
-            });
+        token_client.transfer(&owner, env.current_contract_address(), &amount);
 
         position.principal += amount;
         position.token_address = token_address.clone();
         env.storage().instance().set(&position_key, &position);
+        Self::adjust_legacy_total(&env, &token_address, amount);
 
-        // Track total deposits
-        let total_key = Symbol::new(&env, "total_deposits");
-        let total: i128 = env.storage().instance().get(&total_key).unwrap_or(0);
-        env.storage().instance().set(&total_key, &(total + amount));
+        Ok(())
     }
 
     /// Withdraw principal + yield
-    pub fn withdraw(env: Env, owner: Address, amount: i128) -> i128 {
-        let position_key = Symbol::new(&env, "position");
-        let position: Position = env.storage().instance()
-            .get(&position_key)
-            .unwrap_or_else(|| panic!("no position found"));
+    pub fn withdraw(env: Env, owner: Address, amount: i128) -> Result<i128, Error> {
+        owner.require_auth();
 
-        if position.principal < amount {
-            panic!("insufficient principal");
-        }
-
-        // Calculate yield (1% for demo)
-        let yield_amount = amount / 100; // 1% yield
+        let (token_address, yield_amount) = Self::take_legacy_principal(&env, &owner, amount)?;
         let total_withdraw = amount + yield_amount;
 
-        // Update position
-        let mut updated_position = position.clone();
-        updated_position.principal -= amount;
-        updated_position.yield_earned += yield_amount;
-        
-        env.storage().instance().set(&position_key, &updated_position);
-        
         // Transfer tokens back to the caller (escrow contract)
-        let token_client = token::TokenClient::new(&env, &position.token_address);
+        let token_client = token::TokenClient::new(&env, &token_address);
         token_client.transfer(&env.current_contract_address(), &owner, &total_withdraw);
 
-        total_withdraw
+        Ok(total_withdraw)
     }
 
     /// Withdraw principal to one recipient and send the accrued yield to another recipient (client)
-    pub fn withdraw_to(env: Env, owner: Address, amount: i128, principal_recipient: Address, yield_recipient: Address) -> i128 {
-        let position_key = Symbol::new(&env, "position");
-        let position: Position = env.storage().instance()
-            .get(&position_key)
-            .unwrap_or_else(|| panic!("no position found"));
-
-        if position.principal < amount {
-            panic!("insufficient principal");
-        }
+    pub fn withdraw_to(env: Env, owner: Address, amount: i128, principal_recipient: Address, yield_recipient: Address) -> Result<i128, Error> {
+        owner.require_auth();
 
-        // Calculate yield (1% for demo)
-        let yield_amount = amount / 100; // 1% yield
-        let total_withdraw = amount + yield_amount;
-
-        // Update position
-        let mut updated_position = position.clone();
-        updated_position.principal -= amount;
-        updated_position.yield_earned += yield_amount;
-        
-        env.storage().instance().set(&position_key, &updated_position);
+        let (token_address, yield_amount) = Self::take_legacy_principal(&env, &owner, amount)?;
+        let token_client = token::TokenClient::new(&env, &token_address);
 
         // Transfer principal to the freelancer (principal_recipient)
-        let contract_address = env.current_contract_address();
-        let mut args_principal = soroban_sdk::Vec::new(&env);
-        args_principal.push_back(contract_address.into_val(&env));
-        args_principal.push_back(principal_recipient.into_val(&env));
-        args_principal.push_back(amount.into_val(&env));
-
-        env.invoke_contract::<()>(
-            &position.token_address,
-            &Symbol::new(&env, "transfer"),
-            args_principal,
-        );
+        token_client.transfer(&env.current_contract_address(), &principal_recipient, &amount);
 
         // Transfer yield to the client (yield_recipient) if any
         if yield_amount > 0 {
-            let mut args_yield = soroban_sdk::Vec::new(&env);
-            args_yield.push_back(contract_address.into_val(&env));
-            args_yield.push_back(yield_recipient.into_val(&env));
-            args_yield.push_back(yield_amount.into_val(&env));
-
-            env.invoke_contract::<()>(
-                &position.token_address,
-                &Symbol::new(&env, "transfer"),
-                args_yield,
-            );
+            token_client.transfer(&env.current_contract_address(), &yield_recipient, &yield_amount);
         }
 
-        total_withdraw
+        Ok(amount + yield_amount)
     }
 
     /// Get user balance
     pub fn get_user_balance(env: Env, owner: Address) -> i128 {
-        let position_key = Symbol::new(&env, "position");
+        let position_key = Self::position_key(&env, &owner);
         if let Some(position) = env.storage().instance().get::<_, Position>(&position_key) {
             position.principal + position.yield_earned
         } else {
@@ -169,11 +130,11 @@ impl YieldHarvester {
     }
 
     /// Get position details
-    pub fn get_position(env: Env, owner: Address) -> Position {
-        let position_key = Symbol::new(&env, "position");
+    pub fn get_position(env: Env, owner: Address) -> Result<Position, Error> {
+        let position_key = Self::position_key(&env, &owner);
         env.storage().instance()
             .get(&position_key)
-            .unwrap_or_else(|| panic!("no position found"))
+            .ok_or(Error::NoPosition)
     }
 
     /// Get total deposits
@@ -181,4 +142,175 @@ impl YieldHarvester {
         let total_key = Symbol::new(&env, "total_deposits");
         env.storage().instance().get(&total_key).unwrap_or(0)
     }
+
+    // YieldStrategy interface
+    // Principal earns `base_rate` basis points per year. Yield is paid out of the
+    // tokens this contract holds beyond the principal deposited through the strategy.
+
+    /// Take `amount` of `token` from `owner` and add it to the owner's principal
+    pub fn strategy_deposit(env: Env, owner: Address, token: Address, amount: i128) -> Result<(), Error> {
+        owner.require_auth();
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        token::TokenClient::new(&env, &token).transfer(&owner, env.current_contract_address(), &amount);
+
+        let mut position = Self::accrue(&env, &owner, &token);
+        position.principal += amount;
+        Self::write_strategy_position(&env, &owner, &token, &position);
+        Self::adjust_strategy_total(&env, &token, amount);
+
+        Ok(())
+    }
+
+    /// Send `amount` of the owner's principal to `recipient`
+    pub fn strategy_withdraw_principal(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> Result<i128, Error> {
+        owner.require_auth();
+
+        let mut position = Self::accrue(&env, &owner, &token);
+        if position.principal < amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+        position.principal -= amount;
+        Self::write_strategy_position(&env, &owner, &token, &position);
+        Self::adjust_strategy_total(&env, &token, -amount);
+
+        token::TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &recipient, &amount);
+
+        Ok(amount)
+    }
+
+    /// Send up to `amount` of the owner's accrued yield to `recipient`
+    pub fn strategy_claim_yield(
+        env: Env,
+        owner: Address,
+        token: Address,
+        amount: i128,
+        recipient: Address,
+    ) -> i128 {
+        owner.require_auth();
+
+        let mut position = Self::accrue(&env, &owner, &token);
+
+        // Never pay yield out of other owners' principal
+        let token_client = token::TokenClient::new(&env, &token);
+        let paid = amount.min(position.yield_accrued).min(Self::reserves(&env, &token)).max(0);
+
+        position.yield_accrued -= paid;
+        Self::write_strategy_position(&env, &owner, &token, &position);
+
+        if paid > 0 {
+            token_client.transfer(&env.current_contract_address(), &recipient, &paid);
+        }
+
+        paid
+    }
+
+    /// Principal plus accrued yield currently held for `owner`
+    pub fn strategy_preview_balance(env: Env, owner: Address, token: Address) -> i128 {
+        let position = Self::accrue(&env, &owner, &token);
+        position.principal + position.yield_accrued
+    }
+}
+
+impl YieldHarvester {
+    fn position_key(env: &Env, owner: &Address) -> (Symbol, Address) {
+        (Symbol::new(env, "position"), owner.clone())
+    }
+
+    /// Remove `amount` from the owner's legacy position and work out its 1% yield
+    fn take_legacy_principal(env: &Env, owner: &Address, amount: i128) -> Result<(Address, i128), Error> {
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+
+        let position_key = Self::position_key(env, owner);
+        let mut position: Position = env.storage().instance()
+            .get(&position_key)
+            .ok_or(Error::NoPosition)?;
+
+        if position.principal < amount {
+            return Err(Error::InsufficientPrincipal);
+        }
+
+        // Calculate yield (1% for demo), paid only out of reserves
+        let token_address = position.token_address.clone();
+        let yield_amount = (amount / 100).min(Self::reserves(env, &token_address)).max(0);
+
+        position.principal -= amount;
+        position.yield_earned += yield_amount;
+        env.storage().instance().set(&position_key, &position);
+        Self::adjust_legacy_total(env, &token_address, -amount);
+
+        Ok((token_address, yield_amount))
+    }
+
+    /// Principal held through the legacy entrypoints for a token
+    fn legacy_total(env: &Env, token: &Address) -> i128 {
+        let key = (Symbol::new(env, "legacy_total"), token.clone());
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+
+    /// Adjust the per-token legacy total and the overall deposit total together
+    fn adjust_legacy_total(env: &Env, token: &Address, delta: i128) {
+        let key = (Symbol::new(env, "legacy_total"), token.clone());
+        let total = Self::legacy_total(env, token);
+        env.storage().instance().set(&key, &(total + delta));
+
+        let total_key = Symbol::new(env, "total_deposits");
+        let total: i128 = env.storage().instance().get(&total_key).unwrap_or(0);
+        env.storage().instance().set(&total_key, &(total + delta));
+    }
+
+    /// Tokens held beyond all outstanding principal, legacy and strategy
+    fn reserves(env: &Env, token: &Address) -> i128 {
+        let balance = token::TokenClient::new(env, token).balance(&env.current_contract_address());
+        balance - Self::strategy_total(env, token) - Self::legacy_total(env, token)
+    }
+
+    /// Load a strategy position with yield accrued up to now
+    fn accrue(env: &Env, owner: &Address, token: &Address) -> StrategyPosition {
+        let now = env.ledger().timestamp();
+        let key = (Symbol::new(env, "strategy_position"), owner.clone(), token.clone());
+        let mut position: StrategyPosition = env.storage().instance()
+            .get(&key)
+            .unwrap_or(StrategyPosition {
+                principal: 0,
+                yield_accrued: 0,
+                updated_at: now,
+            });
+
+        let base_rate: u32 = env.storage().instance().get(&Symbol::new(env, "base_rate")).unwrap_or(0);
+        let elapsed = now.saturating_sub(position.updated_at);
+        position.yield_accrued += position.principal * base_rate as i128 * elapsed as i128
+            / (SECONDS_PER_YEAR as i128 * 10000);
+        position.updated_at = now;
+        position
+    }
+
+    fn write_strategy_position(env: &Env, owner: &Address, token: &Address, position: &StrategyPosition) {
+        let key = (Symbol::new(env, "strategy_position"), owner.clone(), token.clone());
+        env.storage().instance().set(&key, position);
+    }
+
+    /// Total principal held through the strategy interface for a token
+    fn strategy_total(env: &Env, token: &Address) -> i128 {
+        let key = (Symbol::new(env, "strategy_total"), token.clone());
+        env.storage().instance().get(&key).unwrap_or(0)
+    }
+
+    fn adjust_strategy_total(env: &Env, token: &Address, delta: i128) {
+        let key = (Symbol::new(env, "strategy_total"), token.clone());
+        let total = Self::strategy_total(env, token);
+        env.storage().instance().set(&key, &(total + delta));
+    }
 }
+
+mod test;
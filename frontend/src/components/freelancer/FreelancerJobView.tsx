@@ -3,7 +3,7 @@ import { motion } from 'framer-motion';
 import { useNavigate, useParams } from 'react-router-dom';
 import Sidebar from '../shared/Sidebar';
 import { useWallet } from '../../hooks/useWallet';
-import { getJobContract, submitProofContract } from '../../lib/contracts';
+import { acceptJobContract, getJobContract, submitProofContract } from '../../lib/contracts';
 import Toast from '../Toast';
 
 interface Milestone {
@@ -38,6 +38,7 @@ export default function FreelancerJobView() {
     const [job, setJob] = useState<Job | null>(null);
     const [loadingJob, setLoadingJob] = useState(true);
     const [submittingProof, setSubmittingProof] = useState<number | null>(null);
+    const [acceptingJob, setAcceptingJob] = useState(false);
     const [proofUrls, setProofUrls] = useState<Record<number, string>>({});
     const [toast, setToast] = useState<{ show: boolean; message: string; type: 'success' | 'error' }>({
         show: false,
@@ -144,7 +145,10 @@ export default function FreelancerJobView() {
                 description: 'Job details fetched from blockchain',
                 totalAmount: 1000,
                 milestones: [],
-                status: 'in_progress',
+                // Jobs wait for the freelancer's acceptance before work starts
+                status: Array.isArray(contractResult.status) && contractResult.status[0] === 'Pending'
+                    ? 'pending'
+                    : 'in_progress',
                 freelancerAddress: contractResult.freelancer || '',
                 clientAddress: contractResult.client || '',
                 createdAt: new Date().toISOString()
@@ -315,6 +319,30 @@ export default function FreelancerJobView() {
         }
     };
 
+    // Accept a pending on-chain job so milestones can be worked on
+    const handleAcceptJob = async () => {
+        if (!connected || !job) {
+            showToast('Please connect your wallet first', 'error');
+            return;
+        }
+
+        setAcceptingJob(true);
+        try {
+            const result = await acceptJobContract(address, job.id, signTransaction);
+            if (result.success) {
+                showToast('✅ Job accepted! You can now start on the milestones.', 'success');
+                await fetchJobDetails();
+            } else {
+                showToast(result.error || 'Failed to accept job', 'error');
+            }
+        } catch (error: any) {
+            console.error('Error accepting job:', error);
+            showToast(error.message || 'Failed to accept job', 'error');
+        } finally {
+            setAcceptingJob(false);
+        }
+    };
+
     const showToast = (message: string, type: 'success' | 'error') => {
         setToast({ show: true, message, type });
     };
@@ -434,6 +462,21 @@ export default function FreelancerJobView() {
                             </div>
                         </div>
 
+                        {isFreelancer && job.status === 'pending' && (
+                            <div className="mt-6 p-4 bg-indigo-50 border border-indigo-200 rounded-xl flex items-center justify-between gap-4">
+                                <p className="text-indigo-800 text-sm font-medium">
+                                    The client has funded this job. Accept it to start working on the milestones.
+                                </p>
+                                <button
+                                    onClick={handleAcceptJob}
+                                    disabled={acceptingJob}
+                                    className="px-6 py-3 bg-indigo-600 hover:bg-indigo-700 disabled:opacity-50 text-white rounded-full font-semibold transition-all duration-200 shadow-sm hover:shadow-md"
+                                >
+                                    {acceptingJob ? 'Accepting...' : 'Accept Job'}
+                                </button>
+                            </div>
+                        )}
+
                         {!isFreelancer && connected && (
                             <motion.div
                                 initial={{ opacity: 0, y: 8 }}
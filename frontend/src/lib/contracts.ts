@@ -64,6 +64,27 @@ export const computeJobId = (counter: number): string => {
  * Escrow Core Contract Interactions
  */
 
+/**
+ * `JobOptions` leaving every setting at its contract default.
+ * Struct fields are encoded as a map with keys in sorted order.
+ */
+const defaultJobOptions = (): xdr.ScVal => {
+    const unset: Record<string, xdr.ScVal> = {
+        milestones: xdr.ScVal.scvVoid(),
+        payees: xdr.ScVal.scvVoid(),
+        review_period: xdr.ScVal.scvVoid(),
+        strategy: xdr.ScVal.scvVec([]),
+        terms: xdr.ScVal.scvVec([]),
+        yield_policy: xdr.ScVal.scvVec([]),
+    };
+    return xdr.ScVal.scvMap(
+        Object.keys(unset).sort().map((key) => new xdr.ScMapEntry({
+            key: xdr.ScVal.scvSymbol(key),
+            val: unset[key],
+        }))
+    );
+};
+
 export const createJobContract = async (
     clientAddress: string,
     freelancerAddress: string,
@@ -89,7 +110,8 @@ export const createJobContract = async (
         toScVal(freelancerAddress, 'address'),
         toScVal(amountStroops, 'i128'),
         toScVal(assetAddress, 'address'), // Always pass as address
-        toScVal(milestoneCount, 'u32')
+        toScVal(milestoneCount, 'u32'),
+        defaultJobOptions()
     ];
 
     const result = await executeContractCall(
@@ -127,20 +149,72 @@ export const createJobContract = async (
     } as any;
 };
 
+/**
+ * Freelancer accepts a pending job. Jobs created without terms commit to
+ * an all-zero terms hash.
+ */
+export const acceptJobContract = async (
+    freelancerAddress: string,
+    jobId: string,
+    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>,
+    termsHash: Uint8Array = new Uint8Array(32)
+) => {
+    const params = [
+        toScVal(stringToBytes32(jobId), 'bytes32'),
+        toScVal(termsHash, 'bytes32')
+    ];
+
+    return await executeContractCall(
+        CONTRACT_IDS.ESCROW_CORE,
+        'accept_job',
+        params,
+        freelancerAddress,
+        signTransaction
+    );
+};
+
+/**
+ * Client takes back a job the freelancer did not accept before its deadline
+ */
+export const withdrawUnacceptedJobContract = async (
+    clientAddress: string,
+    jobId: string,
+    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
+) => {
+    const params = [
+        toScVal(stringToBytes32(jobId), 'bytes32')
+    ];
+
+    return await executeContractCall(
+        CONTRACT_IDS.ESCROW_CORE,
+        'withdraw_unaccepted_job',
+        params,
+        clientAddress,
+        signTransaction
+    );
+};
+
 export const submitProofContract = async (
     freelancerAddress: string,
     jobId: string,
     milestoneId: number,
     proofUrl: string,
-    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
+    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>,
+    contentHash?: Uint8Array
 ) => {
     // Convert jobId string to bytes32
     const jobIdBytes = stringToBytes32(jobId);
+
+    // Without a file hash, commit to the proof URI itself
+    const hashBytes = contentHash ?? new Uint8Array(
+        await crypto.subtle.digest('SHA-256', new TextEncoder().encode(proofUrl))
+    );
     
     const params = [
         toScVal(jobIdBytes, 'bytes32'),
         toScVal(milestoneId, 'u32'),
-        toScVal(proofUrl, 'string')
+        toScVal(proofUrl, 'string'),
+        toScVal(hashBytes, 'bytes32')
     ];
 
     return await executeContractCall(
@@ -214,14 +288,72 @@ export const releasePaymentContract = async (
     return result;
 };
 
-// Fetch all jobs for a client (read-only)
+export const releasePaymentWithConversionContract = async (
+    clientAddress: string,
+    jobId: string,
+    milestoneId: number,
+    targetCurrency: keyof typeof TOKEN_ADDRESSES,
+    minAmountOut: number,
+    deadlineSeconds: number,
+    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
+) => {
+    // Convert jobId string to bytes32
+    const jobIdBytes = stringToBytes32(jobId);
+    const minAmountOutStroops = Math.floor(minAmountOut * 10_000_000);
+    const deadline = Math.floor(Date.now() / 1000) + deadlineSeconds;
+
+    const params = [
+        toScVal(jobIdBytes, 'bytes32'),
+        toScVal(milestoneId, 'u32'),
+        toScVal(TOKEN_ADDRESSES[targetCurrency], 'address'),
+        toScVal(minAmountOutStroops, 'i128'),
+        toScVal(deadline, 'u64')
+    ];
+
+    return await executeContractCall(
+        CONTRACT_IDS.ESCROW_CORE,
+        'release_payment_with_conversion',
+        params,
+        clientAddress,
+        signTransaction
+    );
+};
+
+export const bumpJobContract = async (
+    signerAddress: string,
+    jobId: string,
+    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
+) => {
+    // Convert jobId string to bytes32
+    const jobIdBytes = stringToBytes32(jobId);
+
+    const params = [
+        toScVal(jobIdBytes, 'bytes32')
+    ];
+
+    return await executeContractCall(
+        CONTRACT_IDS.ESCROW_CORE,
+        'bump_job',
+        params,
+        signerAddress,
+        signTransaction
+    );
+};
+
+// Most jobs the contract returns per page of `get_client_jobs`
+const CLIENT_JOBS_PAGE_SIZE = 50;
+
+// Fixed-point scale of LiquidityRouter exchange rates
+const RATE_SCALE = 10_000_000;
+
+// Fetch the first page of jobs for a client (read-only)
 export const getClientJobsContract = async (clientAddress: string) => {
     try {
         console.log('📋 Fetching on-chain jobs for client:', clientAddress);
         const tx = await buildContractTransaction(
             CONTRACT_IDS.ESCROW_CORE,
             'get_client_jobs',
-            [toScVal(clientAddress, 'address')],
+            [toScVal(clientAddress, 'address'), toScVal(0, 'u32'), toScVal(CLIENT_JOBS_PAGE_SIZE, 'u32')],
             'GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF'
         );
 
@@ -351,15 +483,13 @@ export const getExchangeRate = async (
     try {
         const fromAddress = TOKEN_ADDRESSES[fromCurrency as keyof typeof TOKEN_ADDRESSES];
         const toAddress = TOKEN_ADDRESSES[toCurrency as keyof typeof TOKEN_ADDRESSES];
-        const amountStroops = Math.floor(amount * 10_000_000);
 
         const tx = await buildContractTransaction(
             CONTRACT_IDS.LIQUIDITY_ROUTER,
             'get_exchange_rate',
             [
                 toScVal(fromAddress, 'address'),
-                toScVal(toAddress, 'address'),
-                toScVal(amountStroops, 'i128')
+                toScVal(toAddress, 'address')
             ],
             'GAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAWHF' // Dummy for read-only
         );
@@ -367,10 +497,10 @@ export const getExchangeRate = async (
         const built = tx.build();
         const simulated = await simulateTransaction(built);
 
-        // Extract output amount from result
-        const outputStroops = parseInt((simulated as any).returnValue?.toString() || '0');
-        const outputAmount = outputStroops / 10_000_000;
-        const rate = outputAmount / amount;
+        // The router quotes a fixed-point rate scaled by RATE_SCALE
+        const scaledRate = parseInt((simulated as any).returnValue?.toString() || '0');
+        const rate = scaledRate / RATE_SCALE;
+        const outputAmount = amount * rate;
 
         return {
             success: true,
@@ -385,37 +515,6 @@ export const getExchangeRate = async (
     }
 };
 
-export const convertAndSend = async (
-    senderAddress: string,
-    fromCurrency: string,
-    toCurrency: string,
-    amount: number,
-    recipientAddress: string,
-    maxSlippagePercent: number,
-    signTransaction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
-) => {
-    const fromAddress = TOKEN_ADDRESSES[fromCurrency as keyof typeof TOKEN_ADDRESSES];
-    const toAddress = TOKEN_ADDRESSES[toCurrency as keyof typeof TOKEN_ADDRESSES];
-    const amountStroops = Math.floor(amount * 10_000_000);
-    const slippageBps = Math.floor(maxSlippagePercent * 100); // 1% = 100 bps
-
-    const params = [
-        toScVal(fromAddress, 'address'),
-        toScVal(toAddress, 'address'),
-        toScVal(amountStroops, 'i128'),
-        toScVal(recipientAddress, 'address'),
-        toScVal(slippageBps, 'i128')
-    ];
-
-    return await executeContractCall(
-        CONTRACT_IDS.LIQUIDITY_ROUTER,
-        'convert_and_send',
-        params,
-        senderAddress,
-        signTransaction
-    );
-};
-
 /**
  * RWA Yield Harvester Contract Interactions
  */
@@ -4,6 +4,8 @@ import {
     Transaction,
     Networks,
     Contract,
+    Operation,
+    BASE_FEE,
     Address,
     nativeToScVal,
     scValToNative,
@@ -114,6 +116,47 @@ export async function simulateTransaction(tx: Transaction): Promise<Transaction>
     return assembled.build(); // Build it to return a Transaction
 }
 
+/**
+ * Restore archived contract entries (e.g. jobs whose TTL expired) flagged by simulation.
+ * Returns true when a restore transaction was submitted, so the caller rebuilds its transaction.
+ */
+export async function restoreArchivedEntries(
+    tx: Transaction,
+    signerAddress: string,
+    signFunction: (xdr: string, networkPassphrase: string) => Promise<{ success: boolean; signedXdr?: string; error?: string }>
+): Promise<boolean> {
+    const simulated = await server.simulateTransaction(tx);
+    if (!SorobanRpc.Api.isSimulationRestore(simulated)) {
+        return false;
+    }
+
+    console.log('♻️ Restoring archived contract entries...');
+    const account = await server.getAccount(signerAddress);
+    const fee = (parseInt(BASE_FEE) + parseInt(simulated.restorePreamble.minResourceFee)).toString();
+    const restoreTx = new TransactionBuilder(account, {
+        fee,
+        networkPassphrase: NETWORK_PASSPHRASE
+    })
+        .setSorobanData(simulated.restorePreamble.transactionData.build())
+        .addOperation(Operation.restoreFootprint({}))
+        .setTimeout(180)
+        .build();
+
+    const signResult = await signFunction(restoreTx.toXDR(), NETWORK_PASSPHRASE);
+    if (!signResult.success || !signResult.signedXdr) {
+        throw new Error(signResult.error || 'Restore signature failed');
+    }
+
+    const submitResult = await submitTransaction(signResult.signedXdr);
+    const status = await waitForTransaction(submitResult.hash);
+    if (status.status !== 'SUCCESS') {
+        throw new Error(`Restore failed: ${status.status}`);
+    }
+
+    console.log('✅ Archived entries restored');
+    return true;
+}
+
 /**
  * Submit transaction to network
  */
@@ -203,6 +246,8 @@ export function toScVal(value: any, type?: string): xdr.ScVal {
         return nativeToScVal(value, { type: 'i128' });
     } else if (type === 'u32') {
         return nativeToScVal(value, { type: 'u32' });
+    } else if (type === 'u64') {
+        return nativeToScVal(value, { type: 'u64' });
     } else if (type === 'string') {
         return nativeToScVal(value, { type: 'string' });
     } else if (type === 'bytes32') {
@@ -249,7 +294,12 @@ export async function executeContractCall(
 
         // Build transaction builder
         const txBuilder = await buildContractTransaction(contractId, method, params, signerAddress);
-        const tx = txBuilder.build();
+        let tx = txBuilder.build();
+
+        // Restore archived entries first; the account sequence changes, so rebuild afterwards
+        if (await restoreArchivedEntries(tx, signerAddress, signFunction)) {
+            tx = (await buildContractTransaction(contractId, method, params, signerAddress)).build();
+        }
 
         // Simulate - returns Transaction
         console.log('🔍 Simulating transaction...');
@@ -50,6 +50,7 @@ stellar contract invoke \
   --network $NETWORK \
   -- \
   initialize \
+  --admin $DEPLOYER \
   --yield_harvester $VITE_CONTRACT_YIELD_HARVESTER \
   --liquidity_router $VITE_CONTRACT_LIQUIDITY_ROUTER \
   --usdc_token "USDC" \
@@ -57,11 +58,25 @@ stellar contract invoke \
 
 echo "✅ EscrowCore initialized"
 
+# Initialize LiquidityRouter: only EscrowCore may convert through it
+echo "⚙️ Initializing LiquidityRouter..."
+stellar contract invoke \
+  --id $VITE_CONTRACT_LIQUIDITY_ROUTER \
+  --source deployer \
+  --network $NETWORK \
+  -- \
+  initialize \
+  --admin $DEPLOYER \
+  --escrow $VITE_CONTRACT_ESCROW_CORE
+
+echo "✅ LiquidityRouter initialized"
+
 echo ""
 echo "🎉 All contracts initialized successfully!"
 echo ""
 echo "📋 Contract Status:"
 echo "  EscrowCore: ✅ Initialized"
 echo "  YieldHarvester: ✅ Initialized" 
+echo "  LiquidityRouter: ✅ Initialized (set rates and add liquidity before converting)"
 echo ""
 echo "💡 Next: Use the frontend to create jobs and test the flow"